@@ -0,0 +1,546 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Configuration for the macros, which is shared by every invocation in a
+//! crate.
+//!
+//! Configuration comes from the `[package.metadata.semester]` table in the
+//! crate's `Cargo.toml`:
+//!
+//! ```toml
+//! [package.metadata.semester]
+//! stylesheets = ["static/site.css"]
+//! prefix = "acme-"
+//! naming = "kebab"
+//! max-static-conditions = 8
+//! mangle = "always"
+//! strictness = "warn"
+//! tailwind-conflicts = "warn"
+//! strict-idents = true
+//! ```
+//!
+//! Paths can also be set with environment variables, which take precedence
+//! over the manifest.
+
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    env, fs,
+    path::{Path, PathBuf},
+    rc::Rc,
+    time::SystemTime,
+};
+
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use toml::{Table, Value};
+
+use crate::{
+    check_class_name,
+    naming::{Naming, Rule},
+};
+
+/// Environment variable containing a list of stylesheets to check class
+/// names against, separated like `PATH`.
+const STYLESHEETS_VAR: &str = "SEMESTER_STYLESHEETS";
+
+/// Environment variable containing the directory to write class manifests
+/// to.
+const MANIFEST_DIR_VAR: &str = "SEMESTER_MANIFEST_DIR";
+
+/// Environment variable containing the path to the mangling table. Setting
+/// it enables mangling in release builds.
+const MANGLE_VAR: &str = "SEMESTER_MANGLE";
+
+/// Environment variable containing when to mangle class names, like the
+/// `mangle` setting.
+const MANGLE_MODE_VAR: &str = "SEMESTER_MANGLE_MODE";
+
+/// Resolve a path relative to the manifest directory of the crate currently
+/// being compiled.
+pub fn resolve(path: impl AsRef<Path>) -> PathBuf {
+    manifest_dir().join(path)
+}
+
+fn manifest_dir() -> PathBuf {
+    env::var_os("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_default()
+}
+
+/// How violations of optional checks, like checking against stylesheets,
+/// are reported. Checks that protect the correctness of the rendered HTML,
+/// like forbidding whitespace, are always errors.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Level {
+    /// Violations are compile errors.
+    #[default]
+    Deny,
+
+    /// Violations are compile warnings.
+    Warn,
+
+    /// Violations aren't reported.
+    Allow,
+}
+
+/// When class names are mangled, if there's a mangling table.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Mangle {
+    /// Mangle when `semester-macro` itself was compiled without debug
+    /// assertions. With cargo's default profiles, proc macros are compiled
+    /// with the same setting as the crates that use them, so this
+    /// corresponds to release builds, but a `build-override` in the profile
+    /// can change that.
+    #[default]
+    Release,
+
+    /// Always mangle.
+    Always,
+
+    /// Never mangle.
+    Never,
+}
+
+impl Mangle {
+    fn parse(mode: &str) -> Option<Self> {
+        match mode {
+            "release" => Some(Self::Release),
+            "always" => Some(Self::Always),
+            "never" => Some(Self::Never),
+            _ => None,
+        }
+    }
+
+    /// Check if class names should be mangled, given whether the proc macro
+    /// was compiled with debug assertions.
+    fn enabled(self, debug_assertions: bool) -> bool {
+        match self {
+            Self::Release => !debug_assertions,
+            Self::Always => true,
+            Self::Never => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// Stylesheets that every class name must be defined in. If this is
+    /// empty, class names aren't checked.
+    pub stylesheets: Vec<PathBuf>,
+
+    /// Directory to write a manifest of every class name to. If this is
+    /// `None`, no manifest is written.
+    pub manifest_dir: Option<PathBuf>,
+
+    /// Table of mangled class names. If this is `None`, class names aren't
+    /// mangled. Once the configuration is loaded, this is only set if
+    /// `mangle` allows mangling.
+    pub mangle_table: Option<PathBuf>,
+
+    /// When class names are mangled.
+    pub mangle: Mangle,
+
+    /// Prefix applied to every class name, unless it's overridden in the
+    /// macro invocation.
+    pub prefix: Option<String>,
+
+    /// Naming rules that every class name must follow at least one of.
+    pub naming: Option<Naming>,
+
+    /// The largest number of conditions allowed in `static_classes!`, which
+    /// generates 2^n variants for n conditions. If this is `None`, there's
+    /// no limit.
+    pub max_static_conditions: Option<usize>,
+
+    /// How violations of the optional checks are reported.
+    pub strictness: Level,
+
+    /// How conflicting Tailwind utilities are reported. This lint is off by
+    /// default.
+    pub tailwind_conflicts: Level,
+
+    /// Whether every class name must be a CSS identifier, which can be used
+    /// in a selector without escaping.
+    pub strict_idents: bool,
+
+    /// The `Cargo.toml` that the configuration was loaded from, if there was
+    /// one.
+    manifest: Option<PathBuf>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            stylesheets: Vec::new(),
+            manifest_dir: None,
+            mangle_table: None,
+            mangle: Mangle::Release,
+            prefix: None,
+            naming: None,
+            max_static_conditions: None,
+            strictness: Level::Deny,
+            tailwind_conflicts: Level::Allow,
+            strict_idents: false,
+            manifest: None,
+        }
+    }
+}
+
+thread_local! {
+    /// Every macro invocation in a crate loads the same configuration, so it's
+    /// only parsed once per compiler process, unless the manifest changes.
+    static CACHE: RefCell<HashMap<PathBuf, (SystemTime, Rc<Config>)>> = RefCell::new(HashMap::new());
+}
+
+/// Build an error about the configuration.
+fn error(manifest: &Path, message: impl std::fmt::Display) -> syn::Error {
+    syn::Error::new(
+        Span::call_site(),
+        format!(
+            "invalid `[package.metadata.semester]` in `{}`: {message}",
+            manifest.display()
+        ),
+    )
+}
+
+/// Get a string setting from the metadata table.
+fn string<'a>(table: &'a Table, key: &str, manifest: &Path) -> syn::Result<Option<&'a str>> {
+    match table.get(key) {
+        None => Ok(None),
+        Some(Value::String(value)) => Ok(Some(value)),
+        Some(_) => Err(error(manifest, format!("`{key}` must be a string"))),
+    }
+}
+
+/// Get a lint level setting from the metadata table.
+fn level(table: &Table, key: &str, manifest: &Path) -> syn::Result<Option<Level>> {
+    match string(table, key, manifest)? {
+        None => Ok(None),
+        Some("deny") => Ok(Some(Level::Deny)),
+        Some("warn") => Ok(Some(Level::Warn)),
+        Some("allow") => Ok(Some(Level::Allow)),
+        Some(other) => Err(error(
+            manifest,
+            format!("`{key}` must be \"deny\", \"warn\" or \"allow\", not {other:?}"),
+        )),
+    }
+}
+
+/// Parse the `naming` setting, which is a single rule, a list of rules, or a
+/// table of named rules.
+fn parse_naming(naming: &Value, manifest: &Path) -> syn::Result<Naming> {
+    let invalid = || {
+        error(
+            manifest,
+            "`naming` must be a rule, a list of rules, or a table of named rules",
+        )
+    };
+
+    let rules: Vec<(Option<&str>, &Value)> = match naming {
+        Value::String(_) => vec![(None, naming)],
+        Value::Array(rules) => rules.iter().map(|rule| (None, rule)).collect(),
+        Value::Table(rules) => rules
+            .iter()
+            .map(|(name, rule)| (Some(name.as_str()), rule))
+            .collect(),
+        _ => return Err(invalid()),
+    };
+
+    let rules = rules
+        .into_iter()
+        .map(|(name, rule)| match rule {
+            Value::String(rule) => Rule::new(name, rule)
+                .map_err(|err| error(manifest, format!("invalid `naming`: {err}"))),
+            _ => Err(invalid()),
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    match rules.is_empty() {
+        true => Err(error(manifest, "`naming` must have at least one rule")),
+        false => Ok(Naming { rules }),
+    }
+}
+
+/// Parse the `[package.metadata.semester]` table.
+fn parse_metadata(table: &Table, manifest: &Path) -> syn::Result<Config> {
+    let mut config = Config::default();
+
+    for key in table.keys() {
+        if ![
+            "stylesheets",
+            "manifest-dir",
+            "mangle-table",
+            "mangle",
+            "prefix",
+            "naming",
+            "max-static-conditions",
+            "strictness",
+            "tailwind-conflicts",
+            "strict-idents",
+        ]
+        .contains(&key.as_str())
+        {
+            return Err(error(manifest, format!("unknown setting `{key}`")));
+        }
+    }
+
+    config.stylesheets = match table.get("stylesheets") {
+        None => Vec::new(),
+        Some(Value::Array(paths)) => paths
+            .iter()
+            .map(|path| match path {
+                Value::String(path) => Ok(resolve(path)),
+                _ => Err(error(manifest, "`stylesheets` must be a list of paths")),
+            })
+            .collect::<syn::Result<_>>()?,
+        Some(_) => return Err(error(manifest, "`stylesheets` must be a list of paths")),
+    };
+
+    config.manifest_dir = string(table, "manifest-dir", manifest)?.map(resolve);
+    config.mangle_table = string(table, "mangle-table", manifest)?.map(resolve);
+
+    if let Some(mode) = string(table, "mangle", manifest)? {
+        config.mangle = Mangle::parse(mode).ok_or_else(|| {
+            error(
+                manifest,
+                format!("`mangle` must be \"release\", \"always\" or \"never\", not {mode:?}"),
+            )
+        })?;
+    }
+
+    config.prefix = string(table, "prefix", manifest)?
+        .filter(|prefix| !prefix.is_empty())
+        .map(|prefix| match check_class_name(prefix, Span::call_site()) {
+            Ok(()) => Ok(prefix.to_owned()),
+            Err(err) => Err(error(manifest, format!("invalid `prefix`: {err}"))),
+        })
+        .transpose()?;
+
+    config.naming = table
+        .get("naming")
+        .map(|naming| parse_naming(naming, manifest))
+        .transpose()?;
+
+    if let Some(max) = table.get("max-static-conditions") {
+        config.max_static_conditions = Some(
+            max.as_integer()
+                .and_then(|max| usize::try_from(max).ok())
+                .ok_or_else(|| {
+                    error(
+                        manifest,
+                        "`max-static-conditions` must be a non-negative integer",
+                    )
+                })?,
+        );
+    }
+
+    config.strictness = level(table, "strictness", manifest)?.unwrap_or(Level::Deny);
+    config.tailwind_conflicts =
+        level(table, "tailwind-conflicts", manifest)?.unwrap_or(Level::Allow);
+
+    config.strict_idents = match table.get("strict-idents") {
+        None => false,
+        Some(Value::Boolean(strict)) => *strict,
+        Some(_) => return Err(error(manifest, "`strict-idents` must be true or false")),
+    };
+
+    config.manifest = Some(manifest.to_owned());
+    Ok(config)
+}
+
+/// Read and parse the configuration from a `Cargo.toml`.
+fn read_manifest(manifest: &Path) -> syn::Result<Config> {
+    let text = fs::read_to_string(manifest).map_err(|err| error(manifest, err))?;
+    let root: Table = text.parse().map_err(|err| error(manifest, err))?;
+
+    let metadata = root
+        .get("package")
+        .and_then(|package| package.get("metadata"))
+        .and_then(|metadata| metadata.get("semester"));
+
+    match metadata {
+        None => Ok(Config {
+            manifest: Some(manifest.to_owned()),
+            ..Config::default()
+        }),
+        Some(Value::Table(table)) => parse_metadata(table, manifest),
+        Some(_) => Err(error(manifest, "expected a table")),
+    }
+}
+
+/// Load the configuration from the crate's `Cargo.toml`, or get it from the
+/// cache.
+fn load_manifest() -> syn::Result<Rc<Config>> {
+    let manifest = manifest_dir().join("Cargo.toml");
+
+    let Ok(modified) = fs::metadata(&manifest).and_then(|metadata| metadata.modified()) else {
+        // Outside of cargo, there might not be a manifest at all
+        return Ok(Rc::new(Config::default()));
+    };
+
+    CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+
+        match cache.get(&manifest) {
+            Some((cached, config)) if *cached == modified => Ok(config.clone()),
+            _ => {
+                let config = Rc::new(read_manifest(&manifest)?);
+                cache.insert(manifest, (modified, config.clone()));
+                Ok(config)
+            }
+        }
+    })
+}
+
+impl Config {
+    /// Load the configuration for the crate currently being compiled.
+    /// Relative paths are resolved relative to the crate's manifest
+    /// directory.
+    pub fn load() -> syn::Result<Self> {
+        let mut config = Config::clone(&*load_manifest()?);
+
+        if let Some(paths) = env::var_os(STYLESHEETS_VAR) {
+            config.stylesheets = env::split_paths(&paths)
+                .filter(|path| !path.as_os_str().is_empty())
+                .map(resolve)
+                .collect();
+        }
+
+        if let Some(path) = env::var_os(MANIFEST_DIR_VAR).filter(|path| !path.is_empty()) {
+            config.manifest_dir = Some(resolve(path));
+        }
+
+        if let Some(path) = env::var_os(MANGLE_VAR).filter(|path| !path.is_empty()) {
+            config.mangle_table = Some(resolve(path));
+        }
+
+        if let Ok(mode) = env::var(MANGLE_MODE_VAR) {
+            config.mangle = Mangle::parse(&mode).ok_or_else(|| {
+                syn::Error::new(
+                    Span::call_site(),
+                    format!(
+                        "`{MANGLE_MODE_VAR}` must be \"release\", \"always\" or \"never\", \
+                        not {mode:?}"
+                    ),
+                )
+            })?;
+        }
+
+        if !config.mangle.enabled(cfg!(debug_assertions)) {
+            config.mangle_table = None;
+        }
+
+        Ok(config)
+    }
+
+    /// Generate code that makes the compiler track the files and
+    /// environment variables that the configuration was loaded from, so that
+    /// the crate is rebuilt when they change.
+    pub fn tracking(&self) -> TokenStream2 {
+        let manifest = self
+            .manifest
+            .iter()
+            .map(|manifest| manifest.display().to_string());
+
+        quote! {
+            #( const _: &[u8] = ::core::include_bytes!(#manifest); )*
+            const _: ::core::option::Option<&str> = ::core::option_env!(#STYLESHEETS_VAR);
+            const _: ::core::option::Option<&str> = ::core::option_env!(#MANIFEST_DIR_VAR);
+            const _: ::core::option::Option<&str> = ::core::option_env!(#MANGLE_VAR);
+            const _: ::core::option::Option<&str> = ::core::option_env!(#MANGLE_MODE_VAR);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(toml: &str) -> syn::Result<Config> {
+        parse_metadata(&toml.parse().unwrap(), Path::new("Cargo.toml"))
+    }
+
+    #[test]
+    fn test_parse_metadata() {
+        let config = parse(
+            r#"
+                stylesheets = ["site.css"]
+                prefix = "acme-"
+                naming = "^[a-z-]+$"
+                max-static-conditions = 4
+                mangle = "always"
+                strictness = "warn"
+                tailwind-conflicts = "deny"
+                strict-idents = true
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.stylesheets, [resolve("site.css")]);
+        assert_eq!(config.prefix.as_deref(), Some("acme-"));
+        assert!(config.naming.unwrap().rules[0].is_match("btn-primary"));
+        assert_eq!(config.max_static_conditions, Some(4));
+        assert_eq!(config.mangle, Mangle::Always);
+        assert_eq!(config.strictness, Level::Warn);
+        assert_eq!(config.tailwind_conflicts, Level::Deny);
+        assert!(config.strict_idents);
+    }
+
+    #[test]
+    fn test_parse_naming() {
+        let config = parse(
+            r#"
+                [naming]
+                utilities = "kebab"
+                hooks = "^js-[a-z-]+$"
+            "#,
+        )
+        .unwrap();
+
+        let names: Vec<_> = config
+            .naming
+            .unwrap()
+            .rules
+            .into_iter()
+            .map(|rule| rule.name)
+            .collect();
+        assert_eq!(names, ["hooks", "utilities"]);
+
+        let config = parse(r#"naming = ["bem", "^js-"]"#).unwrap();
+        assert_eq!(config.naming.unwrap().rules[0].name, "bem");
+    }
+
+    #[test]
+    fn test_parse_metadata_errors() {
+        assert!(parse("stylesheet = []").is_err());
+        assert!(parse("naming = \"[\"").is_err());
+        assert!(parse("naming = []").is_err());
+        assert!(parse("naming = [\"kebab\", 1]").is_err());
+        assert!(parse("prefix = \"a b\"").is_err());
+        assert!(parse("max-static-conditions = -1").is_err());
+        assert!(parse("strictness = \"forbid\"").is_err());
+        assert!(parse("strict-idents = \"yes\"").is_err());
+        assert!(parse("mangle = \"debug\"").is_err());
+    }
+
+    #[test]
+    fn test_defaults() {
+        let config = parse("").unwrap();
+        assert_eq!(config.mangle, Mangle::Release);
+        assert_eq!(config.max_static_conditions, None);
+        assert_eq!(config.strictness, Level::Deny);
+        assert_eq!(config.tailwind_conflicts, Level::Allow);
+    }
+
+    #[test]
+    fn test_mangle_mode() {
+        assert!(Mangle::Release.enabled(false));
+        assert!(!Mangle::Release.enabled(true));
+        assert!(Mangle::Always.enabled(true));
+        assert!(!Mangle::Never.enabled(false));
+    }
+}
@@ -0,0 +1,436 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Validation of class names against the classes defined in real CSS
+//! stylesheets.
+
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fs,
+    iter::Peekable,
+    path::PathBuf,
+    rc::Rc,
+    str::Chars,
+    time::SystemTime,
+};
+
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::LitStr;
+
+use crate::{check_class_name, config, ClassName};
+
+/// Scan a stylesheet for every class used in a selector. This isn't a real
+/// CSS parser; it just tracks comments, strings and blocks well enough to
+/// find every `.class` in the preludes of rules (the part before the `{`),
+/// including rules nested in at-rules like `@media`.
+pub fn scan_classes(css: &str) -> BTreeSet<String> {
+    let mut classes = BTreeSet::new();
+    let mut prelude = String::new();
+    let mut chars = css.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut previous = None;
+                for c in chars.by_ref() {
+                    if previous == Some('*') && c == '/' {
+                        break;
+                    }
+                    previous = Some(c);
+                }
+            }
+            '"' | '\'' => {
+                // Strings only matter because they might contain braces.
+                // Attribute selectors like `[href=".pdf"]` don't define
+                // classes, so the string isn't part of the prelude.
+                let quote = c;
+                while let Some(c) = chars.next() {
+                    match c {
+                        '\\' => {
+                            chars.next();
+                        }
+                        c if c == quote => break,
+                        _ => {}
+                    }
+                }
+            }
+            '\\' => {
+                prelude.push('\\');
+                if let Some(c) = chars.next() {
+                    prelude.push(c);
+                }
+            }
+            '{' => {
+                scan_prelude(&prelude, &mut classes);
+                prelude.clear();
+            }
+            '}' | ';' => prelude.clear(),
+            c => prelude.push(c),
+        }
+    }
+
+    classes
+}
+
+/// Find every class in a single selector list.
+fn scan_prelude(prelude: &str, classes: &mut BTreeSet<String>) {
+    let prelude = prelude.trim_start();
+
+    // At-rule preludes (`@media (min-width: 1.5em)`) never contain classes,
+    // except for `@scope (.card)`, which is rare enough to ignore.
+    if prelude.starts_with('@') {
+        return;
+    }
+
+    let mut chars = prelude.chars().peekable();
+    let mut in_attribute = false;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '[' => in_attribute = true,
+            ']' => in_attribute = false,
+            '.' if !in_attribute => {
+                if let Some(class) = scan_ident(&mut chars) {
+                    classes.insert(class);
+                }
+            }
+            '\\' => {
+                chars.next();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Scan a CSS identifier, resolving escapes. Returns `None` if the
+/// characters don't start an identifier (for instance, in `50.5%`).
+fn scan_ident(chars: &mut Peekable<Chars<'_>>) -> Option<String> {
+    let mut ident = String::new();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            '\\' => {
+                chars.next();
+                match chars.peek() {
+                    Some(c) if c.is_ascii_hexdigit() => {
+                        let mut code = 0;
+                        let mut digits = 0;
+                        while let Some(digit) = chars.peek().and_then(|c| c.to_digit(16)) {
+                            if digits == 6 {
+                                break;
+                            }
+                            code = code * 16 + digit;
+                            digits += 1;
+                            chars.next();
+                        }
+                        if chars.peek().is_some_and(|c| c.is_whitespace()) {
+                            chars.next();
+                        }
+                        ident.push(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+                    }
+                    Some(_) => ident.extend(chars.next()),
+                    None => break,
+                }
+            }
+            // An unescaped digit can't start an identifier, even after
+            // hyphens
+            c if c.is_ascii_digit() && ident.chars().all(|c| c == '-') => return None,
+            c if c.is_ascii_alphanumeric() || c == '-' || c == '_' || !c.is_ascii() => {
+                ident.push(c);
+                chars.next();
+            }
+            _ => break,
+        }
+    }
+
+    match ident.as_str() {
+        "" | "-" => None,
+        _ => Some(ident),
+    }
+}
+
+/// Compute the edit distance between two strings, counting insertions,
+/// deletions, substitutions and transpositions of adjacent characters as a
+/// single edit each (the "optimal string alignment" distance).
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    let mut previous: Vec<usize> = Vec::new();
+    let mut current: Vec<usize> = (0..=b.len()).collect();
+
+    for i in 1..=a.len() {
+        let mut next = vec![i; b.len() + 1];
+
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            next[j] = (current[j - 1] + cost)
+                .min(current[j] + 1)
+                .min(next[j - 1] + 1);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                next[j] = next[j].min(previous[j - 2] + 1);
+            }
+        }
+
+        previous = current;
+        current = next;
+    }
+
+    current[b.len()]
+}
+
+/// Find the most similar class to `class`, if any are similar enough to be
+/// worth suggesting.
+pub fn suggest<'a>(class: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let threshold = (class.chars().count() / 3).max(1);
+
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(class, candidate), candidate))
+        .filter(|&(distance, _)| distance <= threshold)
+        .min_by_key(|&(distance, _)| distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// The classes defined by a set of stylesheets.
+pub struct Stylesheets {
+    paths: Vec<PathBuf>,
+    classes: BTreeSet<String>,
+}
+
+/// A scanned stylesheet, along with the modification time it was scanned at.
+type CacheEntry = (SystemTime, Rc<BTreeSet<String>>);
+
+thread_local! {
+    /// Stylesheets are scanned once per compiler process, since the same
+    /// files are checked by every macro invocation in a crate. Entries are
+    /// keyed by modification time, because some processes (like
+    /// rust-analyzer) are long-lived.
+    static CACHE: RefCell<HashMap<PathBuf, CacheEntry>> = RefCell::new(HashMap::new());
+}
+
+/// Read and scan a single stylesheet, or get it from the cache.
+fn load_stylesheet(path: &PathBuf) -> syn::Result<Rc<BTreeSet<String>>> {
+    let error = |err| {
+        syn::Error::new(
+            Span::call_site(),
+            format!("failed to read stylesheet `{}`: {err}", path.display()),
+        )
+    };
+
+    let modified = fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .map_err(error)?;
+
+    CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+
+        match cache.get(path) {
+            Some((cached, classes)) if *cached == modified => Ok(classes.clone()),
+            _ => {
+                let css = fs::read_to_string(path).map_err(error)?;
+                let classes = Rc::new(scan_classes(&css));
+                cache.insert(path.clone(), (modified, classes.clone()));
+                Ok(classes)
+            }
+        }
+    })
+}
+
+impl Stylesheets {
+    /// Load and scan every stylesheet in `paths`.
+    pub fn load(paths: &[PathBuf]) -> syn::Result<Self> {
+        let mut classes = BTreeSet::new();
+
+        for path in paths {
+            classes.extend(load_stylesheet(path)?.iter().cloned());
+        }
+
+        Ok(Self {
+            paths: paths.to_vec(),
+            classes,
+        })
+    }
+
+    /// Check that every class in `classes` is defined by these stylesheets.
+    /// All the unknown classes are reported together.
+    pub fn check<'a>(&self, classes: impl IntoIterator<Item = &'a ClassName>) -> syn::Result<()> {
+        classes
+            .into_iter()
+            .filter(|name| !self.classes.contains(&name.class))
+            .map(|name| {
+                let message = match suggest(&name.class, self.classes.iter().map(String::as_str)) {
+                    Some(suggestion) => format!(
+                        "class `{}` is not defined in any stylesheet; did you mean `{suggestion}`?",
+                        name.class
+                    ),
+                    None => format!("class `{}` is not defined in any stylesheet", name.class),
+                };
+
+                syn::Error::new(name.literal.span(), message)
+            })
+            .reduce(|mut combined, error| {
+                combined.combine(error);
+                combined
+            })
+            .map_or(Ok(()), Err)
+    }
+
+    /// Generate code that makes the compiler track the stylesheets, so that
+    /// the crate is rebuilt whenever one of them changes.
+    pub fn tracking(&self) -> TokenStream2 {
+        let paths = self.paths.iter().map(|path| path.display().to_string());
+
+        quote! {
+            #( const _: &[u8] = ::core::include_bytes!(#paths); )*
+        }
+    }
+}
+
+/// A [CSS Module](https://github.com/css-modules/css-modules), which maps
+/// the class names used in source code to the (usually hashed) class names
+/// that are actually rendered. The map is read from a JSON file in the format
+/// written by tools like `postcss-modules`:
+///
+/// ```json
+/// { "button": "button_x7f2a", "active": "active_b81c3" }
+/// ```
+pub struct CssModule {
+    /// The literal naming the module, for error reporting.
+    literal: LitStr,
+
+    /// The path to the JSON map.
+    path: PathBuf,
+
+    names: BTreeMap<String, String>,
+}
+
+impl CssModule {
+    /// Load the map for a CSS module. If `literal` names a JSON file, it's
+    /// used directly; otherwise, `.json` is appended to it, so that
+    /// `button.module.css` is mapped by `button.module.css.json`. Relative
+    /// paths are resolved relative to the crate's manifest directory.
+    pub fn load(literal: &LitStr) -> syn::Result<Self> {
+        let mut path = config::resolve(literal.value());
+
+        if !path
+            .extension()
+            .is_some_and(|extension| extension == "json")
+        {
+            path.as_mut_os_string().push(".json");
+        }
+
+        let error = |message| {
+            syn::Error::new(
+                literal.span(),
+                format!(
+                    "failed to load CSS module map `{}`: {message}",
+                    path.display()
+                ),
+            )
+        };
+
+        let json = fs::read_to_string(&path).map_err(|err| error(err.to_string()))?;
+        let names = serde_json::from_str(&json).map_err(|err| error(err.to_string()))?;
+
+        Ok(Self {
+            literal: literal.clone(),
+            path,
+            names,
+        })
+    }
+
+    /// Replace a class name with the name it's mapped to by this module.
+    /// Classes that aren't in the module are an error.
+    pub fn translate(&self, name: &mut ClassName) -> syn::Result<()> {
+        let span = name.literal.span();
+        let module = self.literal.value();
+
+        let Some(mapped) = self.names.get(&name.class) else {
+            let message = match suggest(&name.class, self.names.keys().map(String::as_str)) {
+                Some(suggestion) => format!(
+                    "class `{}` is not defined in CSS module `{module}`; did you mean `{suggestion}`?",
+                    name.class
+                ),
+                None => format!("class `{}` is not defined in CSS module `{module}`", name.class),
+            };
+
+            return Err(syn::Error::new(span, message));
+        };
+
+        check_class_name(mapped, span).map_err(|err| {
+            syn::Error::new(
+                span,
+                format!(
+                    "CSS module `{module}` maps class `{}` to `{mapped}`, which is invalid: {err}",
+                    name.class
+                ),
+            )
+        })?;
+
+        name.class = mapped.clone();
+        Ok(())
+    }
+
+    /// Generate code that makes the compiler track the module's map, so that
+    /// the crate is rebuilt whenever it changes.
+    pub fn tracking(&self) -> TokenStream2 {
+        let path = self.path.display().to_string();
+
+        quote! {
+            const _: &[u8] = ::core::include_bytes!(#path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_classes() {
+        let css = r#"
+            /* .commented { } */
+            .btn, .btn-primary:hover > a.link[href$=".pdf"] { width: 1.5em; }
+            @media (min-width: 40.5em) {
+                .md\:flex { display: flex; }
+                .w-1\/2 { background: url("a.png"); }
+            }
+            @keyframes pulse { 50.5% { opacity: .5; } }
+            .\31 0 { content: "}"; }
+            .card { &.card--wide { color: red; } }
+        "#;
+
+        let classes = scan_classes(css);
+        assert_eq!(
+            classes.iter().map(String::as_str).collect::<Vec<_>>(),
+            [
+                "10",
+                "btn",
+                "btn-primary",
+                "card",
+                "card--wide",
+                "link",
+                "md:flex",
+                "w-1/2"
+            ],
+        );
+    }
+
+    #[test]
+    fn test_suggest() {
+        let candidates = ["btn", "btn-primary", "btn-secondary", "card"];
+
+        assert_eq!(suggest("btn-primray", candidates), Some("btn-primary"));
+        assert_eq!(suggest("crad", candidates), Some("card"));
+        assert_eq!(suggest("unrelated", candidates), None);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+}
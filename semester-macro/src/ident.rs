@@ -0,0 +1,114 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! The opt-in `strict-idents` check, which requires every class name to be
+//! a CSS `<ident-token>`, so that it can be used in a selector as `.name`
+//! without escaping. See <https://www.w3.org/TR/css-syntax-3/#ident-token-diagram>.
+
+use crate::{char_span, ClassName};
+
+/// Check if a character can appear in an identifier without escaping.
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '-' || c == '_' || !c.is_ascii()
+}
+
+/// Find the first place where a class name breaks the identifier grammar.
+/// Returns the byte offset of the offending character, and the rule it
+/// breaks.
+fn violation(class: &str) -> Option<(usize, String)> {
+    let mut chars = class.chars();
+
+    match (chars.next(), chars.next(), chars.next()) {
+        (Some(c), _, _) if c.is_ascii_digit() => {
+            return Some((0, "identifiers can't start with a digit".to_owned()));
+        }
+        (Some('-'), Some(c), _) if c.is_ascii_digit() => {
+            return Some((
+                1,
+                "identifiers can't start with a hyphen followed by a digit".to_owned(),
+            ));
+        }
+        (Some('-'), None, _) => {
+            return Some((0, "`-` on its own isn't an identifier".to_owned()));
+        }
+        (Some('-'), Some('-'), None) => {
+            return Some((0, "`--` on its own isn't an identifier".to_owned()));
+        }
+        _ => {}
+    }
+
+    class
+        .char_indices()
+        .find(|&(_, c)| !is_name_char(c))
+        .map(|(idx, c)| {
+            (
+                idx,
+                format!(
+                    "`{c}` can't be used in an identifier without escaping; only letters, \
+                    digits, `-`, `_` and non-ASCII characters can"
+                ),
+            )
+        })
+}
+
+/// Check that every class name is a valid identifier. All the errors are
+/// reported together, each at the offending character when the compiler
+/// supports pointing inside of string literals.
+pub fn check(names: &[ClassName]) -> syn::Result<()> {
+    names
+        .iter()
+        .filter_map(|name| {
+            let (idx, rule) = violation(&name.class)?;
+            let character = name.class[..idx].chars().count() + 1;
+
+            // The class name may have a prefix or variants in front of the
+            // literal, or be translated from a CSS module.
+            let literal = name.literal.value();
+            let span = match name.class.strip_suffix(literal.as_str()) {
+                Some(before) if idx >= before.len() => {
+                    let len = name.class[idx..].chars().next().map_or(0, char::len_utf8);
+                    char_span(&name.literal, idx - before.len(), len)
+                }
+                _ => name.literal.span(),
+            };
+
+            Some(syn::Error::new(
+                span,
+                format!(
+                    "class `{}` is not a valid CSS identifier (at character {character}): \
+                    {rule}",
+                    name.class
+                ),
+            ))
+        })
+        .reduce(|mut combined, error| {
+            combined.combine(error);
+            combined
+        })
+        .map_or(Ok(()), Err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_violation() {
+        let offset = |class| violation(class).map(|(idx, _)| idx);
+
+        assert_eq!(offset("btn-primary"), None);
+        assert_eq!(offset("-webkit-box"), None);
+        assert_eq!(offset("_private"), None);
+        assert_eq!(offset("--custom"), None);
+        assert_eq!(offset("3col"), Some(0));
+        assert_eq!(offset("-2x"), Some(1));
+        assert_eq!(offset("-"), Some(0));
+        assert_eq!(offset("--"), Some(0));
+        assert_eq!(offset("a{b"), Some(1));
+        assert_eq!(offset("md:p-4"), Some(2));
+        assert_eq!(offset("w-1/2"), Some(3));
+    }
+}
@@ -6,6 +6,15 @@
 
 extern crate proc_macro;
 
+mod config;
+mod css;
+mod ident;
+mod mangle;
+mod manifest;
+mod naming;
+mod tailwind;
+mod warning;
+
 use std::{
     collections::{hash_map::Entry, HashMap, VecDeque},
     ops::Not,
@@ -16,16 +25,22 @@ use itertools::Itertools as _;
 use joinery::JoinableIterator as _;
 use proc_macro::TokenStream;
 use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
-use quote::quote;
+use quote::{quote, quote_spanned};
 use syn::{
     parse::{Parse, ParseStream},
     parse_macro_input,
     punctuated::Punctuated,
+    spanned::Spanned as _,
     Expr, ExprLit, ExprUnary,
     Lit::Bool,
     LitBool, LitStr, Token, UnOp,
 };
 
+use crate::{
+    config::{Config, Level},
+    css::{CssModule, Stylesheets},
+};
+
 macro_rules! express {
     ( $receiver:ident $(.$method:ident($($args:tt)*))* ) => {{
         let mut value = $receiver;
@@ -36,56 +51,265 @@ macro_rules! express {
     }};
 }
 
+#[derive(Clone)]
 struct ClassName {
     literal: LitStr,
     class: String,
 }
 
+/// Why a class name is invalid.
+struct Invalid {
+    /// The byte range of the offending character, if there is one.
+    at: Option<(usize, usize)>,
+    message: &'static str,
+
+    /// A suggested fix, if there's an obvious one.
+    help: Option<String>,
+}
+
+impl Invalid {
+    /// The full error message, including the suggestion.
+    fn message(&self) -> String {
+        match &self.help {
+            Some(help) => format!("{}\nhelp: {help}", self.message),
+            None => self.message.to_owned(),
+        }
+    }
+}
+
+/// Suggest a fix for a class name containing whitespace.
+fn whitespace_help(class: &str) -> String {
+    let describe = |c: char| match c {
+        ' ' => "space",
+        _ => "whitespace",
+    };
+
+    let trimmed = class.trim();
+    let leading = class.chars().next().filter(|c| c.is_whitespace());
+    let trailing = class.chars().next_back().filter(|c| c.is_whitespace());
+
+    if trimmed.is_empty() {
+        "remove this entry".to_owned()
+    } else if trimmed.contains(char::is_whitespace) {
+        let words = trimmed
+            .split_whitespace()
+            .map(|word| format!("{word:?}"))
+            .collect_vec();
+        let count = match words.len() {
+            2 => "two".to_owned(),
+            3 => "three".to_owned(),
+            n => n.to_string(),
+        };
+        format!(
+            "split into {count} entries: `{}`",
+            words.iter().join_with(", ")
+        )
+    } else {
+        match (leading, trailing) {
+            (Some(c), None) => format!("remove the leading {}", describe(c)),
+            (None, Some(c)) => format!("remove the trailing {}", describe(c)),
+            _ => "remove the leading and trailing whitespace".to_owned(),
+        }
+    }
+}
+
+/// Find the first problem with a class name. With the `unicode` feature,
+/// non-ASCII characters are allowed, other than whitespace and control
+/// characters.
+fn find_invalid(class: &str) -> Option<Invalid> {
+    let find = |pred: &dyn Fn(char) -> bool| {
+        class
+            .char_indices()
+            .find(|&(_, c)| pred(c))
+            .map(|(idx, c)| (idx, c.len_utf8()))
+    };
+
+    if class.is_empty() {
+        Some(Invalid {
+            at: None,
+            message: "class name must not be empty",
+            help: Some("remove this entry".to_owned()),
+        })
+    } else if let Some(at) = find(&char::is_whitespace) {
+        Some(Invalid {
+            at: Some(at),
+            message: "class name must not include whitespace",
+            help: Some(whitespace_help(class)),
+        })
+    } else if let Some(at) = find(&|c| matches!(c, '<' | '>' | '&' | '\'' | '"')) {
+        Some(Invalid {
+            at: Some(at),
+            message: "class name should not include HTML unsafe characters: <>&'\"",
+            help: None,
+        })
+    } else if cfg!(feature = "unicode") {
+        find(&char::is_control).map(|at| Invalid {
+            at: Some(at),
+            message: "class name must not include control characters",
+            help: None,
+        })
+    } else {
+        find(&|c| !c.is_ascii_graphic()).map(|at| Invalid {
+            at: Some(at),
+            message: "class name must be only ascii printable characters",
+            help: class[at.0..].starts_with(|c: char| !c.is_ascii()).then(|| {
+                "enable the `unicode` feature of `semester` to allow non-ASCII class names"
+                    .to_owned()
+            }),
+        })
+    }
+}
+
+/// Check that a class name is valid, reporting any errors at `span`.
+fn check_class_name(class: &str, span: Span) -> syn::Result<()> {
+    match find_invalid(class) {
+        None => Ok(()),
+        Some(invalid) => Err(syn::Error::new(span, invalid.message())),
+    }
+}
+
+/// Check that the value of a string literal is a valid class name, reporting
+/// any errors at the offending character, if possible.
+fn check_literal(literal: &LitStr) -> syn::Result<()> {
+    match find_invalid(&literal.value()) {
+        None => Ok(()),
+        Some(invalid) => {
+            let span = match invalid.at {
+                Some((index, len)) => char_span(literal, index, len),
+                None => literal.span(),
+            };
+            Err(syn::Error::new(span, invalid.message()))
+        }
+    }
+}
+
+/// Combine several errors into one, so that they're all reported together.
+fn combine_errors(errors: impl IntoIterator<Item = syn::Error>) -> syn::Result<()> {
+    errors
+        .into_iter()
+        .reduce(|mut combined, error| {
+            combined.combine(error);
+            combined
+        })
+        .map_or(Ok(()), Err)
+}
+
+/// Get the span of `len` bytes at byte offset `index` in the value of a
+/// string literal. Pointing inside of a literal is only supported by nightly
+/// compilers, and only for literals without escapes, so otherwise this is the
+/// span of the whole literal.
+fn char_span(literal: &LitStr, index: usize, len: usize) -> Span {
+    let token = literal.token();
+    let text = token.to_string();
+
+    // Skip the `r#"` or `"` at the start of the literal
+    match text.find('"') {
+        Some(quote) if !(text.starts_with('"') && text.contains('\\')) => {
+            let start = quote + 1 + index;
+            token.subspan(start..start + len)
+        }
+        _ => None,
+    }
+    .unwrap_or_else(|| literal.span())
+}
+
 impl Parse for ClassName {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let literal: LitStr = input.parse()?;
-        let span = literal.span();
         let class = literal.value();
 
-        if class.is_empty() {
-            Err(syn::Error::new(span, "class name must not be empty"))
-        } else if class.contains(|c: char| c.is_whitespace()) {
-            Err(syn::Error::new(
-                span,
-                "class name must not include whitespace",
-            ))
-        } else if class
-            .as_bytes()
-            .iter()
-            .any(|b| [b'<', b'>', b'&', b'\'', b'"'].contains(b))
-        {
-            Err(syn::Error::new(
-                span,
-                "class name should not include HTML unsafe characters: <>&'\"",
-            ))
-        } else if class.as_bytes().iter().any(|b| b.is_ascii_graphic().not()) {
-            Err(syn::Error::new(
-                span,
-                "class name must be only ascii printable characters",
-            ))
-        } else {
-            Ok(Self { literal, class })
-        }
+        // The class name is checked later, along with all the others, so
+        // that every invalid class name is reported at once.
+        Ok(Self { literal, class })
     }
 }
 
 struct ParsedClassRule {
     id: ClassName,
     condition: Option<Expr>,
+
+    /// If true, the class was written as `::"class"`, and the prefix isn't
+    /// applied to it.
+    global: bool,
+
+    /// The variants from the groups that the class is nested in, like
+    /// `md:hover:`. They're added to the class after the prefix, so that
+    /// `"md:" { "flex" }` with a `tw-` prefix is `md:tw-flex`.
+    variants: String,
 }
 
 impl Parse for ParsedClassRule {
     fn parse(input: ParseStream) -> syn::Result<Self> {
+        let global: Option<Token![::]> = input.parse()?;
         let id = input.parse()?;
         let colon: Option<Token![:]> = input.parse()?;
         let condition = colon.map(move |_| input.parse()).transpose()?;
 
-        Ok(Self { id, condition })
+        Ok(Self {
+            id,
+            condition,
+            global: global.is_some(),
+            variants: String::new(),
+        })
+    }
+}
+
+/// An entry in a list of classes: either a single class, or a group of
+/// classes sharing a variant, like `"md:" { "flex", "gap-4": wide }`.
+enum ParsedEntry {
+    Rule(Box<ParsedClassRule>),
+    Group {
+        variant: LitStr,
+        entries: Punctuated<ParsedEntry, Token![,]>,
+    },
+}
+
+impl Parse for ParsedEntry {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(LitStr) && input.peek2(syn::token::Brace) {
+            let variant: LitStr = input.parse()?;
+
+            let content;
+            syn::braced!(content in input);
+
+            Ok(Self::Group {
+                variant,
+                entries: Punctuated::parse_terminated(&content)?,
+            })
+        } else {
+            input.parse().map(Box::new).map(Self::Rule)
+        }
+    }
+}
+
+impl ParsedEntry {
+    /// Add every literal in this entry that's part of a class name to
+    /// `literals`.
+    fn literals<'a>(&'a self, literals: &mut Vec<&'a LitStr>) {
+        match self {
+            ParsedEntry::Rule(rule) => literals.push(&rule.id.literal),
+            ParsedEntry::Group { variant, entries } => {
+                literals.push(variant);
+                entries.iter().for_each(|entry| entry.literals(literals));
+            }
+        }
+    }
+
+    /// Flatten this entry into a list of classes, adding `variants` to the
+    /// variants of each one.
+    fn flatten(self, variants: &str, rules: &mut Vec<ParsedClassRule>) {
+        match self {
+            ParsedEntry::Rule(mut rule) => {
+                rule.variants = format!("{variants}{}", rule.variants);
+                rules.push(*rule);
+            }
+            ParsedEntry::Group { variant, entries } => {
+                let variants = format!("{variants}{}", variant.value());
+                entries
+                    .into_iter()
+                    .for_each(|entry| entry.flatten(&variants, rules));
+            }
+        }
     }
 }
 
@@ -114,7 +338,6 @@ impl Not for Known {
     type Output = Self;
 
     #[inline]
-    #[must_use]
     fn not(self) -> Self::Output {
         match self {
             Known::True => Known::False,
@@ -189,36 +412,254 @@ struct ClassSpec {
 
 struct Classes {
     rows: Vec<ClassSpec>,
+
+    /// Every class name in the invocation, in order, including the ones
+    /// that were discarded because their condition is known to be false.
+    all: Vec<ClassName>,
+
+    /// Every class name in the invocation as it was written, before the CSS
+    /// module, prefix and variants were applied. Naming rules are checked
+    /// against these.
+    written: Vec<ClassName>,
+
+    /// The CSS module that the class names were translated with, if any.
+    module: Option<CssModule>,
+
+    /// The prefix that was applied to the class names, if any.
+    prefix: Option<String>,
+}
+
+mod kw {
+    syn::custom_keyword!(prefix);
+}
+
+/// Settings for a single macro invocation, which come before the classes.
+#[derive(Default)]
+struct Settings {
+    /// The CSS module from `mod "path";`, if any.
+    module: Option<LitStr>,
+
+    /// The prefix from `prefix = "prefix";`, if any.
+    prefix: Option<LitStr>,
+}
+
+impl Parse for Settings {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut settings = Settings::default();
+
+        // Settings can be in any order, and are each followed by a
+        // semicolon.
+        loop {
+            let (slot, name) = if input.peek(Token![mod]) {
+                input.parse::<Token![mod]>()?;
+                (&mut settings.module, "mod")
+            } else if input.peek(kw::prefix) && input.peek2(Token![=]) {
+                input.parse::<kw::prefix>()?;
+                input.parse::<Token![=]>()?;
+                (&mut settings.prefix, "prefix")
+            } else {
+                break;
+            };
+
+            let value: LitStr = input.parse()?;
+
+            if slot.is_some() {
+                return Err(syn::Error::new(
+                    value.span(),
+                    format!("duplicate `{name}` setting"),
+                ));
+            }
+
+            *slot = Some(value);
+            input.parse::<Token![;]>()?;
+        }
+
+        Ok(settings)
+    }
 }
 
-impl Parse for Classes {
+/// The input to one of the class macros, before the configuration has been
+/// applied to it.
+struct Input {
+    settings: Settings,
+    rules: Vec<ParsedClassRule>,
+
+    /// Every literal that's part of a class name: the classes themselves,
+    /// variants, and BEM blocks and elements. These aren't checked while
+    /// parsing, so that every invalid one can be reported together.
+    literals: Vec<LitStr>,
+}
+
+impl Parse for Input {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        let classes: Punctuated<ParsedClassRule, Token![,]> = Punctuated::parse_terminated(input)?;
+        let settings = input.parse()?;
+        let entries: Punctuated<ParsedEntry, Token![,]> = Punctuated::parse_terminated(input)?;
+
+        let mut literals = Vec::new();
+        entries
+            .iter()
+            .for_each(|entry| entry.literals(&mut literals));
+        let literals = literals.into_iter().cloned().collect();
+
+        let mut rules = Vec::new();
+        entries
+            .into_iter()
+            .for_each(|entry| entry.flatten("", &mut rules));
+
+        Ok(Self {
+            settings,
+            rules,
+            literals,
+        })
+    }
+}
+
+/// The input to the BEM macros: `"block", "element"; "modifier": condition`.
+/// The element is optional. This is converted to an ordinary [`Input`], with
+/// an unconditional `block__element` class followed by a
+/// `block__element--modifier` class for each modifier.
+struct BemInput(Input);
+
+impl Parse for BemInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let settings = input.parse()?;
+
+        let block: LitStr = input.parse()?;
+        let mut literals = Vec::from([block.clone()]);
+        let mut base = ClassName {
+            class: block.value(),
+            literal: block,
+        };
+
+        if input.parse::<Option<Token![,]>>()?.is_some() {
+            let element: LitStr = input.parse()?;
+            literals.push(element.clone());
+            base.class = format!("{}__{}", base.class, element.value());
+            base.literal = element;
+        }
+
+        let modifiers: Punctuated<ParsedClassRule, Token![,]> =
+            match input.parse::<Option<Token![;]>>()? {
+                Some(_) => Punctuated::parse_terminated(input)?,
+                None => Punctuated::new(),
+            };
+
+        combine_errors(
+            modifiers
+                .iter()
+                .filter(|modifier| modifier.global)
+                .map(|modifier| {
+                    syn::Error::new(modifier.id.literal.span(), "BEM modifiers can't be global")
+                }),
+        )?;
+
+        literals.extend(modifiers.iter().map(|modifier| modifier.id.literal.clone()));
+
+        let modifiers = modifiers.into_iter().map(|mut modifier| {
+            modifier.id.class = format!("{}--{}", base.class, modifier.id.class);
+            modifier
+        });
+
+        let base = ParsedClassRule {
+            id: base.clone(),
+            condition: None,
+            global: false,
+            variants: String::new(),
+        };
+
+        Ok(Self(Input {
+            settings,
+            rules: std::iter::once(base).chain(modifiers).collect(),
+            literals,
+        }))
+    }
+}
+
+impl Classes {
+    /// Process the macro input, applying the configuration and checking for
+    /// duplicates. Problems with individual classes are added to `errors`,
+    /// so that they're reported along with the errors from [`prepare`].
+    fn new(input: Input, config: &Config, errors: &mut Vec<syn::Error>) -> syn::Result<Self> {
+        let Input {
+            settings: Settings { module, prefix },
+            rules: mut classes,
+            literals,
+        } = input;
+
+        let module = module.as_ref().map(CssModule::load).transpose()?;
+
+        // Collect every error, rather than stopping at the first one, so that
+        // they can all be fixed at once.
+        errors.extend(
+            literals
+                .iter()
+                .filter_map(|literal| check_literal(literal).err()),
+        );
+
+        let prefix = match prefix {
+            Some(prefix) => {
+                let value = prefix.value();
+                if !value.is_empty() {
+                    errors.extend(check_literal(&prefix).err());
+                }
+                Some(value)
+            }
+            None => config.prefix.clone(),
+        };
+
+        let written = classes.iter().map(|row| row.id.clone()).collect();
+
+        // Translate the class names before anything else, so that all the
+        // other checks apply to the names that will actually be rendered.
+        // Global classes are rendered as written. Otherwise, a CSS module
+        // takes the place of the prefix: its names are already scoped.
+        for row in &mut classes {
+            if !row.global {
+                if let Some(module) = &module {
+                    // Don't look up invalid class names; they've already been
+                    // reported.
+                    if find_invalid(&row.id.literal.value()).is_none() {
+                        errors.extend(module.translate(&mut row.id).err());
+                    }
+                } else if let Some(prefix) = prefix.as_deref() {
+                    row.id.class.insert_str(0, prefix);
+                }
+            }
+
+            row.id.class.insert_str(0, &row.variants);
+        }
 
         // Check for duplicates. Do this before other processing, because we
         // want to flag duplicates even if they're unconditionally rejected.
-        {
-            let mut class_names = HashMap::with_capacity(classes.len());
+        let mut class_names: HashMap<&str, &ParsedClassRule> =
+            HashMap::with_capacity(classes.len());
 
-            classes.iter().try_for_each(move |row| {
-                match class_names.entry(row.id.class.as_str()) {
-                    Entry::Vacant(slot) => {
-                        slot.insert(&row.id.literal);
-                        Ok(())
-                    }
-                    Entry::Occupied(previous) => {
-                        let mut error =
-                            syn::Error::new(row.id.literal.span(), "duplicate class name");
-                        error.combine(syn::Error::new(
-                            previous.get().span(),
-                            "previous occurrence",
-                        ));
-                        Err(error)
-                    }
+        for row in &classes {
+            match class_names.entry(row.id.class.as_str()) {
+                Entry::Vacant(slot) => {
+                    slot.insert(row);
                 }
-            })?;
+                Entry::Occupied(previous) => {
+                    let previous = *previous.get();
+                    let help = match (&previous.condition, &row.condition) {
+                        (Some(_), Some(_)) => "combine the conditions with `||`",
+                        _ => "remove one of them",
+                    };
+
+                    errors.push(syn::Error::new(
+                        row.id.literal.span(),
+                        format!("duplicate class name `{}`\nhelp: {help}", row.id.class),
+                    ));
+                    errors.push(syn::Error::new(
+                        previous.id.literal.span(),
+                        "previous occurrence",
+                    ));
+                }
+            }
         }
 
+        let all = classes.iter().map(|row| row.id.clone()).collect();
+
         let rows = classes
             .into_iter()
             .map(|row| (row.state(), row))
@@ -229,12 +670,32 @@ impl Parse for Classes {
             })
             .collect();
 
-        Ok(Self { rows })
+        Ok(Self {
+            rows,
+            all,
+            written,
+            module,
+            prefix,
+        })
+    }
+
+    /// Wrap every condition in a call to `semester::condition`, so that a
+    /// condition that isn't a `bool` is reported at the condition, with an
+    /// explanation, rather than as a type error in the generated code.
+    fn require_bool_conditions(&mut self) {
+        for condition in self
+            .rows
+            .iter_mut()
+            .filter_map(|row| row.condition.as_mut())
+        {
+            let span = condition.span();
+            *condition = Expr::Verbatim(quote_spanned! {span=> ::semester::condition(#condition) });
+        }
     }
 }
 
 struct NamedCondition {
-    expr: Expr,
+    expr: Box<Expr>,
     field: Ident,
 }
 
@@ -249,19 +710,150 @@ enum NamedClassSpec {
     },
 }
 
-fn fixed_set<'a>(classes: impl Iterator<Item = &'a str> + Clone) -> TokenStream {
+/// Generate `Serialize` and `Deserialize` implementations for a class set
+/// type, if the `serde` feature is enabled. `classes` is every class that
+/// could appear in the set, in order, paired with whether it's unconditional.
+/// `construct` builds the type from `flags`, a `[bool; N]` indicating which of
+/// those classes were present.
+fn serde_impls<'a>(
+    ty: &Ident,
+    classes: impl IntoIterator<Item = (&'a str, bool)>,
+    flags: &Ident,
+    construct: TokenStream2,
+) -> TokenStream2 {
+    if !cfg!(feature = "serde") {
+        return quote! {};
+    }
+
+    let (classes, required): (Vec<&str>, Vec<bool>) = classes.into_iter().unzip();
+
+    quote! {
+        impl ::semester::__serde::Serialize for #ty {
+            #[inline]
+            fn serialize<S: ::semester::__serde::Serializer>(
+                &self,
+                serializer: S,
+            ) -> ::core::result::Result<S::Ok, S::Error> {
+                ::semester::serde::joined::serialize(self, serializer)
+            }
+        }
+
+        impl<'de> ::semester::__serde::Deserialize<'de> for #ty {
+            fn deserialize<D: ::semester::__serde::Deserializer<'de>>(
+                deserializer: D,
+            ) -> ::core::result::Result<Self, D::Error> {
+                let #flags = ::semester::serde::deserialize_flags(
+                    deserializer,
+                    &[ #( #classes , )* ],
+                    &[ #( #required , )* ],
+                )?;
+
+                ::core::result::Result::Ok(#construct)
+            }
+        }
+    }
+}
+
+/// Generate a `ufmt::uDisplay` implementation for a class set type, if the
+/// `ufmt` feature is enabled. `body` writes the classes to `f`, exactly like
+/// the body of the type's `Display` implementation.
+fn ufmt_impl(ty: &Ident, body: &TokenStream2) -> TokenStream2 {
+    if !cfg!(feature = "ufmt") {
+        return quote! {};
+    }
+
+    quote! {
+        impl ::semester::__ufmt::uDisplay for #ty {
+            fn fmt<W: ::semester::__ufmt::uWrite + ?Sized>(
+                &self,
+                f: &mut ::semester::__ufmt::Formatter<'_, W>,
+            ) -> ::core::result::Result<(), W::Error> {
+                #body
+            }
+        }
+    }
+}
+
+/// Generate the trait implementations for each enabled framework
+/// integration for a class set type.
+fn framework_impls(ty: &Ident) -> TokenStream2 {
+    let yew = match cfg!(feature = "yew") {
+        true => quote! { ::semester::__impl_yew!(#ty); },
+        false => quote! {},
+    };
+
+    let leptos = match cfg!(feature = "leptos") {
+        true => quote! { ::semester::__impl_leptos!(#ty); },
+        false => quote! {},
+    };
+
+    let dioxus = match cfg!(feature = "dioxus") {
+        true => quote! { ::semester::__impl_dioxus!(#ty); },
+        false => quote! {},
+    };
+
+    let horrorshow = match cfg!(feature = "horrorshow") {
+        true => quote! { ::semester::__impl_horrorshow!(#ty); },
+        false => quote! {},
+    };
+
+    let maud = match cfg!(feature = "maud") {
+        true => quote! { ::semester::__impl_maud!(#ty); },
+        false => quote! {},
+    };
+
+    let minijinja = match cfg!(feature = "minijinja") {
+        true => quote! { ::semester::__impl_minijinja!(#ty); },
+        false => quote! {},
+    };
+
+    let tera = match cfg!(feature = "tera") {
+        true => quote! { ::semester::__impl_tera!(#ty); },
+        false => quote! {},
+    };
+
+    quote! {
+        #yew
+        #leptos
+        #dioxus
+        #horrorshow
+        #maud
+        #minijinja
+        #tera
+    }
+}
+
+fn fixed_set<'a>(classes: impl Iterator<Item = &'a str> + Clone) -> TokenStream2 {
     let rendered = classes.clone().join_with(' ').to_string();
 
+    let flags = Ident::new("flags", Span::mixed_site());
+    let ty = Ident::new("LocalClasses", Span::call_site());
+    let serde_impls = serde_impls(
+        &ty,
+        classes.clone().map(|class| (class, true)),
+        &flags,
+        quote! {{
+            let _ = #flags;
+            LocalClasses
+        }},
+    );
+
+    let display_body = quote! { f.write_str(#rendered) };
+    let ufmt_impl = ufmt_impl(&ty, &display_body);
+    let framework_impls = framework_impls(&ty);
+
     quote! {::semester::erase_static_classes({
         #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
         struct LocalClasses;
 
         impl ::core::fmt::Display for LocalClasses {
             fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                f.write_str(#rendered)
+                #display_body
             }
         }
 
+        #ufmt_impl
+
         impl ::semester::StaticClasses for LocalClasses {
             #[inline]
             #[must_use]
@@ -277,15 +869,119 @@ fn fixed_set<'a>(classes: impl Iterator<Item = &'a str> + Clone) -> TokenStream
 
         }
 
+        #serde_impls
+
+        #framework_impls
+
         LocalClasses
     })}
-    .into()
+}
+
+/// Run the checks and transformations that are shared by every macro.
+/// Returns code that should be included in the macro's output, to track the
+/// files and environment variables that the checks depend on, and to emit
+/// warnings. `errors` are the errors found so far, which are reported along
+/// with any from these checks.
+fn prepare(
+    classes: &mut Classes,
+    config: &Config,
+    mut errors: Vec<syn::Error>,
+) -> syn::Result<TokenStream2> {
+    let mut tracking = config.tracking();
+
+    if let Some(module) = &classes.module {
+        tracking.extend(module.tracking());
+    }
+
+    // As in `Classes::new`, report every error at once.
+    let mut report = |result: syn::Result<TokenStream2>| match result {
+        Ok(warnings) => tracking.extend(warnings),
+        Err(err) => errors.push(err),
+    };
+
+    if config.strict_idents {
+        // Don't check invalid class names; they've already been reported.
+        let names: Vec<ClassName> = classes
+            .all
+            .iter()
+            .filter(|name| find_invalid(&name.literal.value()).is_none())
+            .cloned()
+            .collect();
+
+        report(ident::check(&names).map(|()| TokenStream2::new()));
+    }
+
+    if !config.stylesheets.is_empty() {
+        let stylesheets = Stylesheets::load(&config.stylesheets)?;
+        report(warning::report(
+            config.strictness,
+            stylesheets.check(&classes.all),
+        ));
+        report(Ok(stylesheets.tracking()));
+    }
+
+    if let Some(naming) = &config.naming {
+        report(warning::report(
+            config.strictness,
+            naming.check(&classes.written),
+        ));
+    }
+
+    if config.tailwind_conflicts != Level::Allow {
+        report(warning::report(
+            config.tailwind_conflicts,
+            tailwind::check(&classes.rows, classes.prefix.as_deref()),
+        ));
+    }
+
+    combine_errors(errors)?;
+
+    if let Some(manifest_dir) = &config.manifest_dir {
+        manifest::record(manifest_dir, &classes.all)?;
+    }
+
+    // This has to be last, since everything else is in terms of the
+    // original class names.
+    if let Some(mangle_table) = &config.mangle_table {
+        mangle::mangle(mangle_table, classes.rows.iter_mut().map(|row| &mut row.id))?;
+    }
+
+    Ok(tracking)
+}
+
+/// Run a macro implementation on its parsed input, after loading and
+/// applying the configuration and doing the shared checks.
+fn expand(
+    input: Input,
+    implementation: impl FnOnce(Classes, &Config) -> syn::Result<TokenStream2>,
+) -> TokenStream {
+    let result = Config::load().and_then(|config| {
+        let mut errors = Vec::new();
+        let mut classes = Classes::new(input, &config, &mut errors)?;
+        let tracking = prepare(&mut classes, &config, errors)?;
+        classes.require_bool_conditions();
+        let output = implementation(classes, &config)?;
+        Ok(quote! {{ #tracking #output }})
+    });
+
+    match result {
+        Ok(output) => output.into(),
+        Err(err) => {
+            // There may be several errors, so they need to be wrapped in a
+            // block to be valid in expression position
+            let errors = err.to_compile_error();
+            quote! {{ #errors }}.into()
+        }
+    }
 }
 
 #[proc_macro]
 pub fn classes_impl(input: TokenStream) -> TokenStream {
-    let classes = parse_macro_input!(input as Classes);
+    let input = parse_macro_input!(input as Input);
+    expand(input, |classes, _| Ok(dynamic_classes(classes)))
+}
 
+fn dynamic_classes(classes: Classes) -> TokenStream2 {
     if classes.rows.iter().all(|row| row.condition.is_none()) {
         return fixed_set(classes.rows.iter().map(|row| row.id.class.as_ref()));
     }
@@ -293,6 +989,8 @@ pub fn classes_impl(input: TokenStream) -> TokenStream {
     let mut field_names =
         (1..).map(|id| quote::format_ident!("condition{id}", span = Span::mixed_site()));
 
+    // coalesce hands back unmerged pairs as an Err, which clippy objects to
+    #[allow(clippy::result_large_err)]
     let class_specs = classes
         .rows
         .into_iter()
@@ -304,7 +1002,7 @@ pub fn classes_impl(input: TokenStream) -> TokenStream {
             Some(expr) => NamedClassSpec::Conditional {
                 id: spec.id,
                 condition: NamedCondition {
-                    expr,
+                    expr: Box::new(expr),
                     field: field_names.next().unwrap(),
                 },
             },
@@ -380,6 +1078,11 @@ pub fn classes_impl(input: TokenStream) -> TokenStream {
         })
         .collect_vec();
 
+    let universe = class_specs.iter().flat_map(|spec| match spec {
+        NamedClassSpec::Conditional { id, .. } => Either::Left([id.class.as_str()].into_iter()),
+        NamedClassSpec::Fixed { ids, .. } => Either::Right(ids.iter().map(|id| id.class.as_str())),
+    });
+
     let iter_class_emissions = class_specs.iter().flat_map(|spec| match spec {
         NamedClassSpec::Conditional {
             id: ClassName { class, .. },
@@ -408,6 +1111,34 @@ pub fn classes_impl(input: TokenStream) -> TokenStream {
         })
         .map(|NamedCondition { field, expr }| quote! { #field : #expr });
 
+    let flags = Ident::new("flags", Span::mixed_site());
+    let serde_impls = serde_impls(
+        &Ident::new("DynamicClassSet", Span::call_site()),
+        class_specs.iter().flat_map(|spec| match spec {
+            NamedClassSpec::Conditional { id, .. } => {
+                Either::Left([(id.class.as_str(), false)].into_iter())
+            }
+            NamedClassSpec::Fixed { ids, .. } => {
+                Either::Right(ids.iter().map(|id| (id.class.as_str(), true)))
+            }
+        }),
+        &flags,
+        {
+            let fields = class_specs
+                .iter()
+                .flat_map(|spec| match spec {
+                    NamedClassSpec::Conditional { condition, .. } => {
+                        Either::Left([Some(&condition.field)].into_iter())
+                    }
+                    NamedClassSpec::Fixed { ids, .. } => Either::Right(ids.iter().map(|_| None)),
+                })
+                .enumerate()
+                .filter_map(|(idx, field)| field.map(|field| quote! { #field: #flags[#idx] }));
+
+            quote! { DynamicClassSet { #( #fields , )* } }
+        },
+    );
+
     let iter_idx_type = match max_len < 250 {
         true => quote! { u8 },
         false => quote! { usize },
@@ -463,6 +1194,26 @@ pub fn classes_impl(input: TokenStream) -> TokenStream {
         quote! {}
     };
 
+    let display_body = quote! {
+        let mut at_least_one = false;
+
+        #(
+            if let Some(class) = #rendered_class_emissions {
+                if at_least_one {
+                    f.write_str(" ")?;
+                }
+                f.write_str(class)?;
+                at_least_one = true;
+            }
+        )*
+
+        Ok(())
+    };
+
+    let ty = Ident::new("DynamicClassSet", Span::call_site());
+    let ufmt_impl = ufmt_impl(&ty, &display_body);
+    let framework_impls = framework_impls(&ty);
+
     quote! {::semester::erase_classes({
         #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
         struct DynamicClassSet {
@@ -471,22 +1222,12 @@ pub fn classes_impl(input: TokenStream) -> TokenStream {
 
         impl ::core::fmt::Display for DynamicClassSet {
             fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
-                let mut at_least_one = false;
-
-                #(
-                    if let Some(class) = #rendered_class_emissions {
-                        if at_least_one {
-                            f.write_str(" ")?;
-                        }
-                        f.write_str(class)?;
-                        at_least_one = true;
-                    }
-                )*
-
-                Ok(())
+                #display_body
             }
         }
 
+        #ufmt_impl
+
         impl ::semester::Classes for DynamicClassSet {
             type Iter = DynamicClassSetIter;
 
@@ -511,6 +1252,11 @@ pub fn classes_impl(input: TokenStream) -> TokenStream {
                 #( #computed_len +)* 0
             }
 
+            #[inline]
+            fn universe(&self) -> &'static [&'static str] {
+                &[ #( #universe , )* ]
+            }
+
             #[must_use]
             #[inline]
             fn iter(&self) -> DynamicClassSetIter {
@@ -562,13 +1308,16 @@ pub fn classes_impl(input: TokenStream) -> TokenStream {
             }
         }
 
+        #serde_impls
+
+        #framework_impls
+
         DynamicClassSet {
             #(
                 #class_set_init_fields ,
             )*
         }
     })}
-    .into()
 }
 
 // In order to avoid an annoying recursive implementation, we use a work queue
@@ -583,8 +1332,71 @@ struct WorkQueueItem<'a> {
 
 #[proc_macro]
 pub fn static_classes_impl(input: TokenStream) -> TokenStream {
-    let classes = parse_macro_input!(input as Classes);
+    let input = parse_macro_input!(input as Input);
+
+    expand(input, |classes, config| {
+        check_static_limit(
+            &classes,
+            config.max_static_conditions,
+            "static_classes!",
+            "classes!",
+        )?;
+        Ok(static_classes(classes))
+    })
+}
+
+#[proc_macro]
+pub fn bem_impl(input: TokenStream) -> TokenStream {
+    let BemInput(input) = parse_macro_input!(input as BemInput);
+    expand(input, |classes, _| Ok(dynamic_classes(classes)))
+}
+
+#[proc_macro]
+pub fn static_bem_impl(input: TokenStream) -> TokenStream {
+    let BemInput(input) = parse_macro_input!(input as BemInput);
+
+    expand(input, |classes, config| {
+        check_static_limit(
+            &classes,
+            config.max_static_conditions,
+            "static_bem!",
+            "bem!",
+        )?;
+        Ok(static_classes(classes))
+    })
+}
+
+/// `static_classes!` and `static_bem!` generate a variant for every
+/// combination of their conditions, so a crate can limit the number of
+/// conditions to keep compile times and binary sizes reasonable. There's no
+/// limit by default. `name` is the macro being expanded, and `dynamic` is the
+/// macro to suggest instead.
+fn check_static_limit(
+    classes: &Classes,
+    max: Option<usize>,
+    name: &str,
+    dynamic: &str,
+) -> syn::Result<()> {
+    let Some(max) = max else {
+        return Ok(());
+    };
+
+    let mut conditions = classes.rows.iter().filter_map(|row| row.condition.as_ref());
+
+    match conditions.nth(max) {
+        None => Ok(()),
+        Some(condition) => Err(syn::Error::new_spanned(
+            condition,
+            format!(
+                "{name} supports at most {max} conditions, because it generates \
+                every combination of them; use {dynamic} instead, or raise \
+                `max-static-conditions` in `[package.metadata.semester]`"
+            ),
+        )),
+    }
+}
 
+fn static_classes(classes: Classes) -> TokenStream2 {
     if classes.rows.iter().all(|row| row.condition.is_none()) {
         return fixed_set(classes.rows.iter().map(|row| row.id.class.as_ref()));
     }
@@ -645,15 +1457,156 @@ pub fn static_classes_impl(input: TokenStream) -> TokenStream {
     }
 
     let conditions = classes.rows.iter().filter_map(|row| row.condition.as_ref());
+    let universe = classes.rows.iter().map(|row| row.id.class.as_str());
+    let universe = quote! { &[ #( #universe , )* ] };
+
+    if !cfg!(feature = "serde") {
+        return quote! {::semester::erase_static_classes({
+            let (class_set, rendered): (&[&str], &str) = match ( #( #conditions , )* ) {
+                #branches
+            };
+
+            unsafe {
+                ::semester::StaticClassSet::new(class_set, rendered).with_universe(#universe)
+            }
+        })};
+    }
+
+    // With serde, we need a local type that knows the full set of classes, so
+    // that it can be deserialized. It wraps the same `StaticClassSet`.
+    let params = classes
+        .rows
+        .iter()
+        .filter(|row| row.condition.is_some())
+        .zip(1..)
+        .map(|(_, id)| quote::format_ident!("condition{id}", span = Span::mixed_site()))
+        .collect_vec();
+
+    let flags = Ident::new("flags", Span::mixed_site());
+    let flag_args = classes
+        .rows
+        .iter()
+        .enumerate()
+        .filter(|(_, row)| row.condition.is_some())
+        .map(|(idx, _)| quote! { #flags[#idx] });
+
+    let ty = Ident::new("LocalStaticClasses", Span::call_site());
+    let framework_impls = framework_impls(&ty);
+    let ufmt_impl = ufmt_impl(
+        &ty,
+        &quote! { f.write_str(::semester::StaticClasses::as_str(&self.0)) },
+    );
+    let serde_impls = serde_impls(
+        &ty,
+        classes
+            .rows
+            .iter()
+            .map(|row| (row.id.class.as_str(), row.condition.is_none())),
+        &flags,
+        quote! { LocalStaticClasses::new( #( #flag_args , )* ) },
+    );
 
     quote! {::semester::erase_static_classes({
-        let (class_set, rendered): (&[&str], &str) = match ( #( #conditions , )* ) {
-            #branches
-        };
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        struct LocalStaticClasses(::semester::StaticClassSet);
+
+        impl LocalStaticClasses {
+            #[inline]
+            fn new( #( #params : bool , )* ) -> Self {
+                let (class_set, rendered): (&[&str], &str) = match ( #( #params , )* ) {
+                    #branches
+                };
 
-        unsafe {
-            ::semester::StaticClassSet::new(class_set, rendered)
+                Self(unsafe {
+                    ::semester::StaticClassSet::new(class_set, rendered).with_universe(#universe)
+                })
+            }
         }
+
+        impl ::core::fmt::Display for LocalStaticClasses {
+            #[inline]
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::fmt::Display::fmt(&self.0, f)
+            }
+        }
+
+        #ufmt_impl
+
+        impl ::semester::StaticClasses for LocalStaticClasses {
+            #[inline]
+            fn as_str(&self) -> &'static str {
+                ::semester::StaticClasses::as_str(&self.0)
+            }
+
+            #[inline]
+            fn class_set(&self) -> &'static [&'static str] {
+                ::semester::StaticClasses::class_set(&self.0)
+            }
+
+            #[inline]
+            fn static_universe(&self) -> &'static [&'static str] {
+                ::semester::StaticClasses::static_universe(&self.0)
+            }
+        }
+
+        #serde_impls
+
+        #framework_impls
+
+        LocalStaticClasses::new( #( #conditions , )* )
     })}
-    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn help(class: &str) -> Option<String> {
+        find_invalid(class).and_then(|invalid| invalid.help)
+    }
+
+    #[test]
+    fn test_find_invalid() {
+        assert!(find_invalid("btn-primary").is_none());
+        assert_eq!(find_invalid("a b").unwrap().at, Some((1, 1)));
+        assert_eq!(find_invalid("a<b").unwrap().at, Some((1, 1)));
+        assert_eq!(find_invalid("").unwrap().at, None);
+    }
+
+    /// Errors from the different checks are all reported together.
+    #[test]
+    fn test_prepare_errors() {
+        let input: Input = syn::parse_str(r#""w-1/2", "card", "card""#).unwrap();
+        let mut config = Config::default();
+        config.strict_idents = true;
+
+        let mut errors = Vec::new();
+        let mut classes = Classes::new(input, &config, &mut errors).unwrap();
+        let err = prepare(&mut classes, &config, errors).unwrap_err();
+
+        let messages: Vec<String> = err.into_iter().map(|err| err.to_string()).collect();
+        assert_eq!(messages.len(), 3);
+        assert!(messages[0].starts_with("duplicate class name `card`"));
+        assert!(messages[2].starts_with("class `w-1/2` is not a valid CSS identifier"));
+    }
+
+    #[test]
+    fn test_suggestions() {
+        assert_eq!(
+            help("a b").unwrap(),
+            r#"split into two entries: `"a", "b"`"#
+        );
+        assert_eq!(
+            help("a  b\tc").unwrap(),
+            r#"split into three entries: `"a", "b", "c"`"#
+        );
+        assert_eq!(help("btn ").unwrap(), "remove the trailing space");
+        assert_eq!(help("\tbtn").unwrap(), "remove the leading whitespace");
+        assert_eq!(
+            help(" btn ").unwrap(),
+            "remove the leading and trailing whitespace"
+        );
+        assert_eq!(help("  ").unwrap(), "remove this entry");
+        assert_eq!(help("a&b"), None);
+    }
 }
@@ -0,0 +1,203 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Replacement of class names with short, opaque mangled names, for smaller
+//! HTML in release builds.
+//!
+//! Mangled names are assigned through a table, shared by every crate that
+//! uses the same table file, which maps each original class name to its
+//! mangled name. The table is also how the mangled names get into the CSS:
+//! the CSS build step applies the same renaming to the stylesheets.
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs::{File, OpenOptions},
+    io::{self, Read as _, Seek as _, Write as _},
+    path::Path,
+};
+
+use fs4::fs_std::FileExt as _;
+use proc_macro2::Span;
+
+use crate::ClassName;
+
+/// The length of a mangled name, unless it has to be longer to avoid a
+/// collision.
+const MIN_LENGTH: usize = 4;
+
+/// Hash a class name, with 64 bit FNV-1a. This needs to be stable across
+/// compiler versions and platforms, so `std`'s hashers aren't suitable.
+fn hash(class: &str) -> u64 {
+    class.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+/// Compute the candidate mangled names for a class, in order of preference.
+/// Each candidate is one character longer than the previous one. They all
+/// start with a letter, so they're valid CSS identifiers.
+fn candidates(class: &str) -> impl Iterator<Item = String> {
+    const LETTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+    const ALPHANUMERICS: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789";
+
+    let mut bits = hash(class);
+    let mut name = String::from(LETTERS[(bits % 26) as usize] as char);
+    bits /= 26;
+
+    // Past 12 characters the hash runs out of bits, so fall back to
+    // numbering the candidates; this is practically unreachable.
+    let hashed = std::iter::from_fn(move || {
+        name.push(ALPHANUMERICS[(bits % 36) as usize] as char);
+        bits /= 36;
+        Some(name.clone())
+    })
+    .skip(MIN_LENGTH - 2)
+    .take(12 - MIN_LENGTH + 1);
+
+    let prefix = format!("z{:x}", hash(class));
+    let fallback = (0..).map(move |n| format!("{prefix}{n}"));
+
+    hashed.chain(fallback)
+}
+
+/// Read the table from an open file. An empty file is an empty table.
+fn read_table(file: &mut File) -> io::Result<BTreeMap<String, String>> {
+    let mut json = String::new();
+    file.read_to_string(&mut json)?;
+
+    match json.trim().is_empty() {
+        true => Ok(BTreeMap::new()),
+        false => serde_json::from_str(&json).map_err(io::Error::from),
+    }
+}
+
+/// Assign a mangled name to a class, which must not already be in the table.
+fn assign(table: &mut BTreeMap<String, String>, used: &mut HashSet<String>, class: &str) -> String {
+    let mangled = candidates(class)
+        .find(|candidate| !used.contains(candidate))
+        .expect("candidates are infinite");
+
+    used.insert(mangled.clone());
+    table.insert(class.to_owned(), mangled.clone());
+    mangled
+}
+
+/// Replace every class name in `names` with its mangled name, from the table
+/// at `path`. Classes that aren't in the table yet are added to it. The file
+/// is locked while it's being updated, since other crates using the same
+/// table may be compiled at the same time.
+pub fn mangle<'a>(
+    path: &Path,
+    names: impl IntoIterator<Item = &'a mut ClassName>,
+) -> syn::Result<()> {
+    let error = |err: io::Error| {
+        syn::Error::new(
+            Span::call_site(),
+            format!(
+                "failed to update mangling table `{}`: {err}",
+                path.display()
+            ),
+        )
+    };
+
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+        .map_err(error)?;
+
+    file.lock_exclusive().map_err(error)?;
+
+    let mut table = read_table(&mut file).map_err(error)?;
+    let mut used: HashSet<String> = table.values().cloned().collect();
+    let count = table.len();
+
+    for name in names {
+        name.class = match table.get(&name.class) {
+            Some(mangled) => mangled.clone(),
+            None => assign(&mut table, &mut used, &name.class),
+        };
+    }
+
+    if table.len() != count {
+        let mut json =
+            serde_json::to_string_pretty(&table).map_err(|err| error(io::Error::from(err)))?;
+        json.push('\n');
+
+        file.set_len(0)
+            .and_then(|()| file.rewind())
+            .and_then(|()| file.write_all(json.as_bytes()))
+            .map_err(error)?;
+    }
+
+    // The lock is released when the file is closed
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_candidates() {
+        let candidates: Vec<String> = candidates("btn-primary").take(3).collect();
+
+        assert_eq!(candidates.len(), 3);
+        assert_eq!(candidates[0].len(), MIN_LENGTH);
+        assert!(candidates[1].starts_with(&candidates[0]));
+        assert!(candidates[0].starts_with(|c: char| c.is_ascii_lowercase()));
+
+        // Mangled names must be stable across builds
+        assert_eq!(
+            candidates[0],
+            super::candidates("btn-primary").next().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_assign_collision() {
+        let mut table = BTreeMap::new();
+        let mut used = HashSet::new();
+
+        let first = candidates("card").next().unwrap();
+        used.insert(first.clone());
+
+        let assigned = assign(&mut table, &mut used, "card");
+        assert_ne!(assigned, first);
+        assert!(assigned.starts_with(&first));
+        assert_eq!(table["card"], assigned);
+    }
+
+    #[test]
+    fn test_mangle_table() {
+        let path =
+            std::env::temp_dir().join(format!("semester-mangle-test-{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let name = |class: &str| ClassName {
+            literal: syn::LitStr::new(class, Span::call_site()),
+            class: class.to_owned(),
+        };
+
+        let mut names = [name("p-4"), name("card")];
+        mangle(&path, &mut names).unwrap();
+
+        let table: BTreeMap<String, String> =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(table.len(), 2);
+        assert_eq!(names[0].class, table["p-4"]);
+        assert_ne!(names[0].class, "p-4");
+
+        // Existing entries are reused
+        let mut names = [name("card")];
+        mangle(&path, &mut names).unwrap();
+        assert_eq!(names[0].class, table["card"]);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
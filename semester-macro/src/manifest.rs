@@ -0,0 +1,139 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! A manifest of every class name used by a crate, for tools like PurgeCSS
+//! or Tailwind that need to know which classes are in use but can't see
+//! inside Rust macros.
+
+use std::{
+    cell::RefCell,
+    collections::{BTreeSet, HashMap},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use proc_macro2::Span;
+
+use crate::ClassName;
+
+thread_local! {
+    /// Every class seen so far in this compiler process, for each manifest
+    /// file. Every macro invocation rewrites the whole manifest, so that by
+    /// the end of compilation it contains every class in the crate.
+    static SEEN: RefCell<HashMap<PathBuf, BTreeSet<String>>> = RefCell::new(HashMap::new());
+}
+
+/// Render a set of classes as a JSON array, one class per line. The classes
+/// are sorted, so the output doesn't depend on the order in which macros are
+/// expanded.
+fn render(classes: &BTreeSet<String>) -> String {
+    let mut out = serde_json::to_string_pretty(classes).expect("strings are always valid JSON");
+    out.push('\n');
+    out
+}
+
+/// Get the file name of the manifest for a crate. A package's library and
+/// binary can have the same crate name, so binaries (and their test
+/// harnesses, which cargo also gives a `CARGO_BIN_NAME`) get their own
+/// manifest, rather than overwriting the library's.
+fn file_name(crate_name: &str, bin_name: Option<&str>) -> String {
+    match bin_name {
+        None => format!("{crate_name}.json"),
+        Some(_) => format!("{crate_name}.bin.json"),
+    }
+}
+
+/// Record the classes from a single macro invocation in the manifest for
+/// the crate currently being compiled, which is in `directory` (see
+/// [`file_name`]). All of the classes are recorded, including ones that are
+/// never rendered because their condition is known to be false.
+///
+/// Nothing is written when the macro is expanded by rust-analyzer. Its
+/// process lives for the whole editing session, so `SEEN` would keep classes
+/// that have since been removed, and the manifest would be rewritten on
+/// every keystroke.
+pub fn record<'a>(
+    directory: &Path,
+    classes: impl IntoIterator<Item = &'a ClassName>,
+) -> syn::Result<()> {
+    if std::env::var_os("RUST_ANALYZER_INTERNALS_DO_NOT_USE").is_some() {
+        return Ok(());
+    }
+
+    let crate_name = std::env::var("CARGO_CRATE_NAME").unwrap_or_else(|_| "crate".to_owned());
+    let bin_name = std::env::var("CARGO_BIN_NAME").ok();
+    let path = directory.join(file_name(&crate_name, bin_name.as_deref()));
+
+    SEEN.with(|seen| {
+        let mut seen = seen.borrow_mut();
+        let (seen, is_new) = match seen.get_mut(&path) {
+            Some(seen) => (seen, false),
+            None => (seen.entry(path.clone()).or_default(), true),
+        };
+
+        let count = seen.len();
+        seen.extend(classes.into_iter().map(|name| name.class.clone()));
+
+        // The manifest from a previous build might contain classes that
+        // aren't used anymore, so it's always rewritten at least once.
+        if !is_new && seen.len() == count {
+            return Ok(());
+        }
+
+        let manifest = render(seen);
+
+        if fs::read_to_string(&path).is_ok_and(|existing| existing == manifest) {
+            return Ok(());
+        }
+
+        fs::create_dir_all(directory)
+            .and_then(|()| fs::write(&path, manifest))
+            .map_err(|err| {
+                syn::Error::new(
+                    Span::call_site(),
+                    format!("failed to write class manifest `{}`: {err}", path.display()),
+                )
+            })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let classes = ["btn", "active", "w-1/2", "a\\b"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        assert_eq!(
+            render(&classes),
+            "[\n  \"a\\\\b\",\n  \"active\",\n  \"btn\",\n  \"w-1/2\"\n]\n"
+        );
+        assert_eq!(render(&BTreeSet::new()), "[]\n");
+
+        assert_eq!(
+            render(&BTreeSet::from(["a\"b".to_owned(), "\u{1}".to_owned()])),
+            "[\n  \"\\u0001\",\n  \"a\\\"b\"\n]\n"
+        );
+    }
+
+    #[test]
+    fn test_file_name() {
+        // A library and a binary in the same package share a crate name
+        let lib = file_name("site", None);
+        let bin = file_name("site", Some("site"));
+
+        assert_eq!(lib, "site.json");
+        assert_eq!(bin, "site.bin.json");
+        assert_ne!(lib, bin);
+
+        // Other binaries already have their own crate names
+        assert_eq!(file_name("site_cli", Some("site-cli")), "site_cli.bin.json");
+    }
+}
@@ -0,0 +1,158 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Naming rules for class names, from the `naming` setting. A rule is either
+//! one of the predefined conventions, or a regular expression. When there
+//! are several rules, every class name has to follow at least one of them,
+//! so a project can allow, say, kebab-case utilities and `js-` hook classes.
+
+use itertools::Itertools as _;
+use regex::Regex;
+
+use crate::ClassName;
+
+/// The predefined conventions, with a description for error messages and
+/// the pattern that implements them.
+const CONVENTIONS: &[(&str, &str, &str)] = &[
+    (
+        "kebab",
+        "kebab-case, like `btn-primary`",
+        r"^[a-z][a-z0-9]*(?:-[a-z0-9]+)*$",
+    ),
+    (
+        "bem",
+        "BEM, like `card__title--large`",
+        r"^[a-z][a-z0-9]*(?:-[a-z0-9]+)*(?:__[a-z][a-z0-9]*(?:-[a-z0-9]+)*)?(?:--[a-z0-9]+(?:-[a-z0-9]+)*)?$",
+    ),
+    (
+        "suit",
+        "SUIT CSS, like `Card-title--large`, `is-active` or `u-textCenter`",
+        r"^(?:(?:[a-z][a-zA-Z0-9]*-)?[A-Z][a-zA-Z0-9]*(?:-[a-z][a-zA-Z0-9]*)*(?:--[a-z][a-zA-Z0-9]*)?|is-[a-z][a-zA-Z0-9]*|u-(?:[a-z0-9]+-)?[a-z][a-zA-Z0-9]*)$",
+    ),
+];
+
+/// A single naming rule.
+#[derive(Debug, Clone)]
+pub struct Rule {
+    /// The name of the rule, for error messages. This is the name of the
+    /// convention or the pattern, unless the rule was given a name in the
+    /// configuration.
+    pub name: String,
+
+    /// What the rule requires, for error messages.
+    description: String,
+
+    regex: Regex,
+}
+
+impl Rule {
+    /// Create a rule from a convention name, like `kebab`, or a regular
+    /// expression.
+    pub fn new(name: Option<&str>, rule: &str) -> Result<Self, String> {
+        let (description, regex) = match CONVENTIONS.iter().find(|&&(id, _, _)| id == rule) {
+            Some(&(_, description, pattern)) => (
+                description.to_owned(),
+                Regex::new(pattern).expect("convention patterns are valid"),
+            ),
+            None => (
+                format!("matching `{rule}`"),
+                Regex::new(rule).map_err(|err| format!("`{rule}` is not a valid regex: {err}"))?,
+            ),
+        };
+
+        Ok(Self {
+            name: name.unwrap_or(rule).to_owned(),
+            description,
+            regex,
+        })
+    }
+
+    pub fn is_match(&self, class: &str) -> bool {
+        self.regex.is_match(class)
+    }
+}
+
+/// The naming rules that every class name must follow at least one of.
+#[derive(Debug, Clone)]
+pub struct Naming {
+    pub rules: Vec<Rule>,
+}
+
+impl Naming {
+    /// Check that every class in `classes` follows at least one of the
+    /// rules. All the violations are reported together.
+    pub fn check<'a>(&self, classes: impl IntoIterator<Item = &'a ClassName>) -> syn::Result<()> {
+        classes
+            .into_iter()
+            .filter(|name| !self.rules.iter().any(|rule| rule.is_match(&name.class)))
+            .map(|name| {
+                let message = match self.rules.as_slice() {
+                    [rule] => format!(
+                        "class `{}` breaks the naming rule `{}`: class names must be {}",
+                        name.class, rule.name, rule.description
+                    ),
+                    rules => format!(
+                        "class `{}` doesn't follow any of the naming rules: {}",
+                        name.class,
+                        rules
+                            .iter()
+                            .map(|rule| format!("`{}` ({})", rule.name, rule.description))
+                            .join(", ")
+                    ),
+                };
+
+                syn::Error::new(name.literal.span(), message)
+            })
+            .reduce(|mut combined, error| {
+                combined.combine(error);
+                combined
+            })
+            .map_or(Ok(()), Err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(rule: &str, class: &str) -> bool {
+        Rule::new(None, rule).unwrap().is_match(class)
+    }
+
+    #[test]
+    fn test_conventions() {
+        assert!(matches("kebab", "btn-primary"));
+        assert!(matches("kebab", "h1"));
+        assert!(!matches("kebab", "btnPrimary"));
+        assert!(!matches("kebab", "btn--primary"));
+        assert!(!matches("kebab", "-btn"));
+
+        assert!(matches("bem", "card"));
+        assert!(matches("bem", "card__title"));
+        assert!(matches("bem", "main-nav__item--is-active"));
+        assert!(!matches("bem", "card__title__text"));
+        assert!(!matches("bem", "Card"));
+
+        assert!(matches("suit", "Card"));
+        assert!(matches("suit", "Card-title--large"));
+        assert!(matches("suit", "acme-Card"));
+        assert!(matches("suit", "is-active"));
+        assert!(matches("suit", "u-textCenter"));
+        assert!(matches("suit", "u-sm-textCenter"));
+        assert!(!matches("suit", "card"));
+        assert!(!matches("suit", "Card_title"));
+    }
+
+    #[test]
+    fn test_rules() {
+        let rule = Rule::new(Some("hooks"), "^js-").unwrap();
+        assert_eq!(rule.name, "hooks");
+        assert!(rule.is_match("js-toggle"));
+
+        assert_eq!(Rule::new(None, "kebab").unwrap().name, "kebab");
+        assert!(Rule::new(None, "[").is_err());
+    }
+}
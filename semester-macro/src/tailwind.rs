@@ -0,0 +1,230 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! A lint for conflicting [Tailwind](https://tailwindcss.com/) utilities in
+//! the same class set, like `"p-2", "p-4": big`. When both are active, the
+//! order of the rules in the generated stylesheet decides which one wins,
+//! rather than the order in the macro, which is almost never what was
+//! intended.
+//!
+//! This knows the most common utility groups, and it errs on the side of
+//! not reporting anything: classes it doesn't recognize never conflict.
+//! The groups themselves are in [`groups`], which is shared with the runtime
+//! `semester` crate, so that the lint and `Classes::merge` always agree.
+
+// `groups.rs` is a copy of the runtime crate's `tailwind.rs`. The copies must
+// stay identical, which `test_groups_shared` checks.
+mod groups;
+
+use quote::ToTokens;
+use syn::{Expr, ExprUnary, UnOp};
+
+use crate::ClassSpec;
+
+/// The parts of a Tailwind class that determine what it conflicts with.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Utility<'a> {
+    /// The variants, like `md:hover:`. Only utilities with the same variants
+    /// conflict.
+    pub variants: &'a str,
+
+    /// Whether the utility has the `!` important modifier.
+    pub important: bool,
+
+    pub group: &'static str,
+}
+
+/// Split a class into its variants and the utility itself. The last `:`
+/// outside of square brackets ends the variants, so `md:[&>*]:p-2` has the
+/// variants `md:[&>*]:`.
+fn split_variants(class: &str) -> (&str, &str) {
+    let mut depth = 0usize;
+    let mut split = 0;
+
+    for (i, c) in class.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth = depth.saturating_sub(1),
+            ':' if depth == 0 => split = i + 1,
+            _ => {}
+        }
+    }
+
+    class.split_at(split)
+}
+
+/// Parse a Tailwind class. `prefix` is the configured prefix, if any, which
+/// goes after the variants. Returns `None` for classes that aren't
+/// recognized.
+pub fn parse<'a>(class: &'a str, prefix: Option<&str>) -> Option<Utility<'a>> {
+    let (variants, utility) = split_variants(class);
+
+    let (important, utility) = match utility.strip_prefix('!') {
+        Some(utility) => (true, utility),
+        None => match utility.strip_suffix('!') {
+            Some(utility) => (true, utility),
+            None => (false, utility),
+        },
+    };
+
+    let utility = prefix
+        .and_then(|prefix| utility.strip_prefix(prefix))
+        .unwrap_or(utility);
+
+    Some(Utility {
+        variants,
+        important,
+        group: groups::group(utility)?,
+    })
+}
+
+/// Check if two utility groups conflict.
+pub fn groups_conflict(a: &str, b: &str) -> bool {
+    a == b || groups::includes(a, b) || groups::includes(b, a)
+}
+
+/// Remove parentheses and invisible groups from an expression.
+fn peel(expr: &Expr) -> &Expr {
+    match expr {
+        Expr::Paren(paren) => peel(&paren.expr),
+        Expr::Group(group) => peel(&group.expr),
+        expr => expr,
+    }
+}
+
+/// Check if two conditions are obviously mutually exclusive, because one is
+/// the negation of the other, like `big` and `!big`.
+fn exclusive(a: &Expr, b: &Expr) -> bool {
+    let negates = |a: &Expr, b: &Expr| match peel(a) {
+        Expr::Unary(ExprUnary {
+            op: UnOp::Not(_),
+            expr,
+            ..
+        }) => peel(expr).to_token_stream().to_string() == peel(b).to_token_stream().to_string(),
+        _ => false,
+    };
+
+    negates(a, b) || negates(b, a)
+}
+
+/// Check a class set for conflicting utilities that can be active at the same
+/// time. All the conflicts are reported together.
+pub fn check(rows: &[ClassSpec], prefix: Option<&str>) -> syn::Result<()> {
+    let utilities: Vec<_> = rows
+        .iter()
+        .map(|row| parse(&row.id.class, prefix))
+        .collect();
+
+    let mut errors = Vec::new();
+
+    for (i, (row, utility)) in rows.iter().zip(&utilities).enumerate() {
+        let Some(utility) = utility else { continue };
+
+        let conflict = rows[..i]
+            .iter()
+            .zip(&utilities)
+            .filter_map(|(previous, other)| Some((previous, other.as_ref()?)))
+            .find(|(previous, other)| {
+                other.variants == utility.variants
+                    && other.important == utility.important
+                    && groups_conflict(other.group, utility.group)
+                    && match (&previous.condition, &row.condition) {
+                        (Some(a), Some(b)) => !exclusive(a, b),
+                        _ => true,
+                    }
+            });
+
+        if let Some((previous, other)) = conflict {
+            let groups = match other.group == utility.group {
+                true => format!("both set {}", utility.group),
+                false => format!("{} and {} overlap", other.group, utility.group),
+            };
+
+            errors.push(syn::Error::new(
+                row.id.literal.span(),
+                format!(
+                    "Tailwind class `{}` conflicts with `{}` ({groups}); when both are \
+                    active, the stylesheet decides which one applies, not the order here",
+                    row.id.class, previous.id.class
+                ),
+            ));
+        }
+    }
+
+    errors
+        .into_iter()
+        .reduce(|mut combined, error| {
+            combined.combine(error);
+            combined
+        })
+        .map_or(Ok(()), Err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn group(class: &str) -> Option<&'static str> {
+        parse(class, None).map(|utility| utility.group)
+    }
+
+    #[test]
+    fn test_groups() {
+        assert_eq!(group("p-2"), Some("padding"));
+        assert_eq!(group("px-2"), Some("padding-x"));
+        assert_eq!(group("-mt-4"), Some("margin-t"));
+        assert_eq!(group("flex"), Some("display"));
+        assert_eq!(group("flex-1"), Some("flex"));
+        assert_eq!(group("flex-col"), Some("flex-direction"));
+        assert_eq!(group("text-lg"), Some("font-size"));
+        assert_eq!(group("text-red-500"), Some("text-color"));
+        assert_eq!(group("text-center"), Some("text-align"));
+        assert_eq!(group("border"), Some("border-width"));
+        assert_eq!(group("border-2"), Some("border-width"));
+        assert_eq!(group("border-dashed"), Some("border-style"));
+        assert_eq!(group("border-t-blue-500"), Some("border-color-t"));
+        assert_eq!(group("rounded-tl-lg"), Some("rounded-tl"));
+        assert_eq!(group("w-[3rem]"), Some("width"));
+        assert_eq!(group("btn-primary"), None);
+        assert_eq!(group("pointer"), None);
+    }
+
+    #[test]
+    fn test_variants() {
+        let utility = parse("md:hover:!tw-p-2", Some("tw-")).unwrap();
+
+        assert_eq!(utility.variants, "md:hover:");
+        assert!(utility.important);
+        assert_eq!(utility.group, "padding");
+
+        assert_eq!(parse("[&>*]:p-2", None).unwrap().variants, "[&>*]:");
+    }
+
+    #[test]
+    fn test_groups_conflict() {
+        assert!(groups_conflict("padding", "padding"));
+        assert!(groups_conflict("padding", "padding-x"));
+        assert!(groups_conflict("padding-l", "padding-x"));
+        assert!(!groups_conflict("padding-x", "padding-y"));
+        assert!(!groups_conflict("padding", "margin"));
+    }
+
+    /// The groups are copied from the runtime crate. Make sure that the
+    /// copies haven't drifted apart.
+    #[test]
+    fn test_groups_shared() {
+        let runtime =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../semester/src/tailwind.rs");
+
+        // Outside of the workspace, like in the published package, there's
+        // nothing to compare against.
+        let Ok(runtime) = std::fs::read_to_string(runtime) else {
+            return;
+        };
+
+        assert_eq!(include_str!("tailwind/groups.rs"), runtime);
+    }
+}
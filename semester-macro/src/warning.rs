@@ -0,0 +1,41 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Compile warnings. Proc macros can't emit warnings directly on stable Rust,
+//! so each warning is generated as the use of a deprecated constant, with the
+//! warning as the deprecation note.
+
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{quote, quote_spanned};
+
+use crate::config::Level;
+
+/// Generate code that emits a warning at `span`.
+pub fn warning(span: Span, message: &str) -> TokenStream2 {
+    let usage = quote_spanned! {span=> let _ = semester_warning; };
+
+    quote! {
+        const _: () = {
+            #[deprecated(note = #message)]
+            #[allow(non_upper_case_globals)]
+            const semester_warning: () = ();
+            #usage
+        };
+    }
+}
+
+/// Report the result of an optional check at the given level. Errors are
+/// either returned as-is, or converted to warnings.
+pub fn report(level: Level, result: syn::Result<()>) -> syn::Result<TokenStream2> {
+    match (level, result) {
+        (_, Ok(())) | (Level::Allow, Err(_)) => Ok(TokenStream2::new()),
+        (Level::Deny, Err(err)) => Err(err),
+        (Level::Warn, Err(err)) => Ok(err
+            .into_iter()
+            .map(|err| warning(err.span(), &err.to_string()))
+            .collect()),
+    }
+}
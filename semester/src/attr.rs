@@ -0,0 +1,96 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use core::fmt::{self, Display, Formatter};
+
+use crate::Classes;
+
+/**
+An HTML attribute containing a set of classes, created by [`Classes::attr`].
+When displayed, it renders as ` class="..."`, with a leading space, or as
+nothing at all if the set of classes is empty. This makes it suitable for
+inserting directly into an HTML tag:
+
+```rust
+use semester::{classes, Classes as _};
+
+let active = true;
+let classes = classes!("button", "active": active);
+assert_eq!(format!("<div{}>", classes.attr()), r#"<div class="button active">"#);
+
+let classes = classes!("active": !active);
+assert_eq!(format!("<div{}>", classes.attr()), "<div>");
+```
+
+The attribute name is written verbatim, and the classes produced by the
+macros are checked at compile time for HTML unsafe characters, so no escaping
+is done for them.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Attr<'a, T> {
+    name: &'a str,
+    classes: T,
+}
+
+impl<'a, T> Attr<'a, T> {
+    #[inline]
+    #[must_use]
+    pub(crate) fn new(name: &'a str, classes: T) -> Self {
+        Self { name, classes }
+    }
+}
+
+impl<C: Classes> Display for Attr<'_, C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.classes.len() {
+            0 => Ok(()),
+            _ => write!(f, " {}=\"{}\"", self.name, self.classes),
+        }
+    }
+}
+
+/// Adapter that HTML-escapes everything written through it.
+#[cfg(feature = "alloc")]
+struct Escaped<'a, 'f>(&'a mut Formatter<'f>);
+
+#[cfg(feature = "alloc")]
+impl fmt::Write for Escaped<'_, '_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        s.split_inclusive(['<', '>', '&', '\'', '"'])
+            .try_for_each(|chunk| match chunk.char_indices().last() {
+                Some((idx, c)) => {
+                    let escape = match c {
+                        '<' => "&lt;",
+                        '>' => "&gt;",
+                        '&' => "&amp;",
+                        '\'' => "&#39;",
+                        '"' => "&quot;",
+                        _ => return self.0.write_str(chunk),
+                    };
+
+                    self.0.write_str(&chunk[..idx])?;
+                    self.0.write_str(escape)
+                }
+                None => Ok(()),
+            })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Display for Attr<'_, &crate::ClassList> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        // `ClassList` validates its classes, but they still come from
+        // runtime strings, so escape them anyway.
+        match self.classes.is_empty() {
+            true => Ok(()),
+            false => {
+                write!(f, " {}=\"", self.name)?;
+                fmt::Write::write_fmt(&mut Escaped(f), format_args!("{}", self.classes))?;
+                f.write_str("\"")
+            }
+        }
+    }
+}
@@ -0,0 +1,105 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+/*!
+[Dioxus](https://docs.rs/dioxus/) integration.
+
+When the `dioxus` feature is enabled, every set created by [`classes`] or
+[`static_classes`] implements Dioxus' `IntoAttributeValue`, so it can be used
+directly as an attribute value, instead of being formatted into a string
+with `class: "{classes}"`:
+
+```rust
+use dioxus::prelude::*;
+use semester::classes;
+
+fn app() -> Element {
+    let active = true;
+    rsx! {
+        button { class: classes!("button", "active": active), "Click" }
+    }
+}
+
+let mut dom = VirtualDom::new(app);
+dom.rebuild_in_place();
+assert_eq!(
+    dioxus_ssr::render(&dom),
+    r#"<button class="button active">Click</button>"#,
+);
+```
+
+Dioxus attribute values always own their text (`AttributeValue::Text` holds a
+`String`, and even a `&'static str` is copied into one), so this allocates a
+single `String` of exactly the right size. If
+[`try_as_str`][Classes::try_as_str] succeeds, the pre-rendered string is
+handed to Dioxus' own `&str` conversion, without going through `core::fmt`. An
+empty set of classes becomes `AttributeValue::None`, which removes the
+attribute, and doesn't allocate at all.
+
+[`ClassList`][crate::ClassList] implements the same trait.
+
+[`classes`]: crate::classes
+[`static_classes`]: crate::static_classes
+*/
+
+use ::dioxus_core::{AttributeValue, IntoAttributeValue};
+
+use crate::{ClassList, Classes, StaticClassSet};
+
+/// Render some classes into a `String` of exactly the right size. `classes`
+/// is called twice: once to measure the classes, and once to render them.
+fn render_exact<'a, I: Iterator<Item = &'a str>>(classes: impl Fn() -> I) -> String {
+    let mut rendered = String::with_capacity(crate::rendered_len(classes()));
+
+    classes().for_each(|class| {
+        if !rendered.is_empty() {
+            rendered.push(' ');
+        }
+        rendered.push_str(class);
+    });
+
+    rendered
+}
+
+/// Convert a class set to an [`AttributeValue`]. An empty set becomes
+/// `AttributeValue::None`; otherwise the classes are rendered into an
+/// `AttributeValue::Text`.
+#[must_use]
+pub fn to_attribute_value(classes: &impl Classes) -> AttributeValue {
+    match (classes.len(), classes.try_as_str()) {
+        (0, _) => AttributeValue::None,
+        (_, Some(rendered)) => rendered.into_value(),
+        (_, None) => {
+            AttributeValue::Text(render_exact(|| classes.iter().map(|class| class as &str)))
+        }
+    }
+}
+
+/// Implement Dioxus' `IntoAttributeValue` for a `Classes` type. Used by code
+/// generated by `semester-macro`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_dioxus {
+    ($type:ty) => {
+        impl $crate::__dioxus_core::IntoAttributeValue for $type {
+            #[inline]
+            fn into_value(self) -> $crate::__dioxus_core::AttributeValue {
+                $crate::dioxus::to_attribute_value(&self)
+            }
+        }
+    };
+}
+
+__impl_dioxus!(StaticClassSet);
+
+impl IntoAttributeValue for ClassList {
+    fn into_value(self) -> AttributeValue {
+        match self.is_empty() {
+            true => AttributeValue::None,
+            false => AttributeValue::Text(render_exact(|| self.iter())),
+        }
+    }
+}
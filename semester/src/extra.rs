@@ -0,0 +1,129 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use core::fmt::{self, Display, Formatter};
+
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, string::String};
+
+use crate::{validate_class_name, Classes};
+
+/**
+A set of classes combined with extra classes from a runtime string, created
+by [`Classes::with_extra`] or [`Classes::with_extra_owned`]. This is intended
+for components that accept an additional `class` string from their caller.
+
+The extra string is split on whitespace. Any extra classes that aren't valid
+class names (see [`validate_class_name`]) or that duplicate a class that's
+already present are dropped. The extra classes are always rendered after the
+classes from the set.
+
+```rust
+use semester::{classes, Classes as _};
+
+let active = true;
+let classes = classes!("button", "active": active);
+
+assert_eq!(
+    classes.with_extra("wide active <bad> wide").to_string(),
+    "button active wide",
+);
+
+// Nothing is allocated if there are no extra classes
+assert_eq!(classes.with_extra("  ").render(), "button active");
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WithExtra<C, S> {
+    classes: C,
+    extra: S,
+}
+
+impl<C: Classes, S: AsRef<str>> WithExtra<C, S> {
+    #[inline]
+    #[must_use]
+    pub(crate) fn new(classes: C, extra: S) -> Self {
+        Self { classes, extra }
+    }
+
+    /// Get an iterator over the extra classes that will actually be
+    /// rendered.
+    fn extra_classes(&self) -> impl Iterator<Item = &str> + '_ {
+        let extra = self.extra.as_ref();
+        let classes = self.classes;
+
+        extra
+            .split_whitespace()
+            .enumerate()
+            .filter(move |&(idx, class)| {
+                validate_class_name(class).is_ok()
+                    && !classes.iter().any(|existing| existing == class)
+                    && !extra
+                        .split_whitespace()
+                        .take(idx)
+                        .any(|previous| previous == class)
+            })
+            .map(|(_, class)| class)
+    }
+
+    /// Get an iterator over all the classes in this set, including the extra
+    /// classes.
+    pub fn iter(&self) -> impl Iterator<Item = &str> + '_ {
+        self.classes
+            .iter()
+            .map(|class| class as &str)
+            .chain(self.extra_classes())
+    }
+
+    /// Get the number of classes in this set, including the extra classes.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.classes.len() + self.extra_classes().count()
+    }
+
+    /// Check if this set has no classes at all.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Render all the classes by separating each one with a space. This will
+    /// only allocate if there are extra classes, or if
+    /// [`Classes::render`] would.
+    #[must_use]
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn render(&self) -> Cow<'_, str> {
+        let mut extra = self.extra_classes().peekable();
+
+        match extra.peek() {
+            None => self.classes.render(),
+            Some(_) => {
+                let mut rendered = String::from(self.classes.render());
+
+                extra.for_each(|class| {
+                    if !rendered.is_empty() {
+                        rendered.push(' ');
+                    }
+                    rendered.push_str(class);
+                });
+
+                Cow::Owned(rendered)
+            }
+        }
+    }
+}
+
+impl<C: Classes, S: AsRef<str>> Display for WithExtra<C, S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.iter().enumerate().try_for_each(|(idx, class)| {
+            if idx > 0 {
+                f.write_str(" ")?;
+            }
+            f.write_str(class)
+        })
+    }
+}
@@ -0,0 +1,220 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use core::fmt::{self, Display, Formatter};
+
+use alloc::{collections::BTreeSet, vec::Vec};
+
+use crate::{validate_class_name, ClassList, ClassNameError, Classes};
+
+/**
+A `ClassFilter` is an allowlist of class names, used to sanitize untrusted
+class strings, such as theme classes from configuration.
+
+The allowlist can be built from a static slice of class names, or from the
+[`universe`][Classes::universe] of a set of classes created by the macros,
+which means that it will allow any class that the set could possibly contain.
+Classes whose condition is a literal `false` can never appear in the set, so
+they aren't part of its universe and aren't allowed. Sets that weren't created
+by the macros have an empty universe unless they provide one.
+Filtering splits the input on whitespace and rejects any class that isn't a
+valid class name (see [`validate_class_name`]) or isn't in the allowlist.
+
+# Example
+
+```rust
+use semester::{classes, ClassFilter, Rejection};
+
+let dark = false;
+let filter = ClassFilter::from_slice(&["theme-light", "theme-dark"])
+    .with_classes(&classes!("rounded", "shadow": dark));
+
+let filtered = filter.filter("theme-dark shadow onclick=\"alert()\" bold");
+assert_eq!(filtered.classes.to_string(), "theme-dark shadow");
+assert_eq!(filtered.rejected.len(), 2);
+assert_eq!(filtered.rejected[1].class, "bold");
+assert_eq!(filtered.rejected[1].reason, Rejection::NotAllowed);
+
+assert!(filter.try_filter("rounded").is_ok());
+assert!(filter.try_filter("rounded bold").is_err());
+```
+*/
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClassFilter {
+    allowed: BTreeSet<&'static str>,
+}
+
+impl ClassFilter {
+    /// Create a new, empty `ClassFilter`, which rejects everything.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            allowed: BTreeSet::new(),
+        }
+    }
+
+    /// Create a `ClassFilter` that allows every class in `classes`.
+    #[must_use]
+    pub fn from_slice(classes: &[&'static str]) -> Self {
+        Self::new().with_slice(classes)
+    }
+
+    /// Create a `ClassFilter` that allows every class that could appear in
+    /// `classes`.
+    #[must_use]
+    pub fn from_classes(classes: &impl Classes) -> Self {
+        Self::from_slice(classes.universe())
+    }
+
+    /// Add every class in `classes` to the allowlist.
+    #[must_use]
+    pub fn with_slice(mut self, classes: &[&'static str]) -> Self {
+        self.allowed.extend(classes);
+        self
+    }
+
+    /// Add every class that could appear in `classes` to the allowlist.
+    #[must_use]
+    pub fn with_classes(self, classes: &impl Classes) -> Self {
+        self.with_slice(classes.universe())
+    }
+
+    /// Check if a single class passes this filter. On success, returns the
+    /// class from the allowlist.
+    pub fn check(&self, class: &str) -> Result<&'static str, Rejection> {
+        validate_class_name(class).map_err(Rejection::Invalid)?;
+
+        self.allowed
+            .get(class)
+            .copied()
+            .ok_or(Rejection::NotAllowed)
+    }
+
+    /// Filter a whitespace-separated string of classes, dropping any that
+    /// don't pass this filter. The dropped classes are reported in
+    /// [`Filtered::rejected`].
+    #[must_use]
+    pub fn filter<'a>(&self, classes: &'a str) -> Filtered<'a> {
+        let mut filtered = Filtered {
+            classes: ClassList::new(),
+            rejected: Vec::new(),
+        };
+
+        classes
+            .split_whitespace()
+            .for_each(|class| match self.check(class) {
+                Ok(class) => {
+                    filtered
+                        .classes
+                        .insert(class)
+                        .expect("allowed classes are always valid");
+                }
+                Err(reason) => filtered.rejected.push(Rejected { class, reason }),
+            });
+
+        filtered
+    }
+
+    /// Filter a whitespace-separated string of classes, failing if any of
+    /// them don't pass this filter.
+    pub fn try_filter<'a>(&self, classes: &'a str) -> Result<ClassList, FilterError<'a>> {
+        self.filter(classes).into_result()
+    }
+}
+
+/// The reason a class was rejected by a [`ClassFilter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rejection {
+    /// The class isn't a valid class name.
+    Invalid(ClassNameError),
+
+    /// The class isn't in the allowlist.
+    NotAllowed,
+}
+
+impl Display for Rejection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Rejection::Invalid(err) => Display::fmt(err, f),
+            Rejection::NotAllowed => f.write_str("class is not in the allowlist"),
+        }
+    }
+}
+
+/// A class that was rejected by a [`ClassFilter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rejected<'a> {
+    /// The rejected class, exactly as it appeared in the input.
+    pub class: &'a str,
+
+    /// Why the class was rejected.
+    pub reason: Rejection,
+}
+
+impl Display for Rejected<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}: {}", self.class, self.reason)
+    }
+}
+
+/// The result of [`ClassFilter::filter`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Filtered<'a> {
+    /// The classes that passed the filter.
+    pub classes: ClassList,
+
+    /// The classes that were rejected by the filter, in order.
+    pub rejected: Vec<Rejected<'a>>,
+}
+
+impl<'a> Filtered<'a> {
+    /// Convert this into a `Result`, which is an error if any classes were
+    /// rejected.
+    pub fn into_result(self) -> Result<ClassList, FilterError<'a>> {
+        match self.rejected.is_empty() {
+            true => Ok(self.classes),
+            false => Err(FilterError {
+                rejected: self.rejected,
+            }),
+        }
+    }
+}
+
+/// Error returned by [`ClassFilter::try_filter`] when one or more classes
+/// were rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilterError<'a> {
+    rejected: Vec<Rejected<'a>>,
+}
+
+impl<'a> FilterError<'a> {
+    /// Get all the classes that were rejected. This is never empty.
+    #[inline]
+    #[must_use]
+    pub fn rejected(&self) -> &[Rejected<'a>] {
+        &self.rejected
+    }
+}
+
+impl Display for FilterError<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("rejected classes: ")?;
+
+        self.rejected
+            .iter()
+            .enumerate()
+            .try_for_each(|(idx, rejected)| {
+                if idx > 0 {
+                    f.write_str(", ")?;
+                }
+                Display::fmt(rejected, f)
+            })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FilterError<'_> {}
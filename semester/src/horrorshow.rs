@@ -0,0 +1,103 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+/*!
+[horrorshow](https://docs.rs/horrorshow/) integration.
+
+When the `horrorshow` feature is enabled, every set created by [`classes`] or
+[`static_classes`] implements horrorshow's `RenderOnce`, `RenderMut`, and
+`Render` traits, so it can be used directly in a template, including as an
+attribute value. The classes are written straight into the template buffer
+through their `Display` implementation, without building an intermediate
+`String`. The classes produced by the macros are checked at compile time for
+HTML unsafe characters, so no escaping is done for them.
+
+```rust
+use horrorshow::{html, Template};
+use semester::classes;
+
+let active = true;
+let classes = classes!("button", "active": active);
+
+let rendered = html! {
+    button(class = &classes) : "Click"
+}
+.into_string()
+.unwrap();
+
+assert_eq!(rendered, r#"<button class="button active">Click</button>"#);
+```
+
+[`ClassList`][crate::ClassList] implements the same traits. Its classes come
+from runtime strings, so they are escaped.
+
+[`classes`]: crate::classes
+[`static_classes`]: crate::static_classes
+*/
+
+use core::fmt::Write as _;
+
+use ::horrorshow::{Render, RenderMut, RenderOnce, TemplateBuffer};
+
+use crate::{ClassList, Classes, StaticClassSet};
+
+/// Write a set of classes directly into a template buffer, without escaping.
+pub fn render(classes: &impl Classes, tmpl: &mut TemplateBuffer<'_>) {
+    // Errors writing to the buffer are recorded in the buffer itself, and
+    // reported when the template is finished.
+    let _ = write!(tmpl.as_raw_writer(), "{}", classes);
+}
+
+/// Implement horrorshow's render traits for a `Classes` type. Used by code
+/// generated by `semester-macro`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_horrorshow {
+    ($type:ty) => {
+        impl $crate::__horrorshow::RenderOnce for $type {
+            #[inline]
+            fn render_once(self, tmpl: &mut $crate::__horrorshow::TemplateBuffer<'_>) {
+                $crate::horrorshow::render(&self, tmpl)
+            }
+        }
+
+        impl $crate::__horrorshow::RenderMut for $type {
+            #[inline]
+            fn render_mut(&mut self, tmpl: &mut $crate::__horrorshow::TemplateBuffer<'_>) {
+                $crate::horrorshow::render(self, tmpl)
+            }
+        }
+
+        impl $crate::__horrorshow::Render for $type {
+            #[inline]
+            fn render(&self, tmpl: &mut $crate::__horrorshow::TemplateBuffer<'_>) {
+                $crate::horrorshow::render(self, tmpl)
+            }
+        }
+    };
+}
+
+__impl_horrorshow!(StaticClassSet);
+
+impl RenderOnce for ClassList {
+    #[inline]
+    fn render_once(self, tmpl: &mut TemplateBuffer<'_>) {
+        Render::render(&self, tmpl)
+    }
+}
+
+impl RenderMut for ClassList {
+    #[inline]
+    fn render_mut(&mut self, tmpl: &mut TemplateBuffer<'_>) {
+        Render::render(self, tmpl)
+    }
+}
+
+impl Render for ClassList {
+    fn render(&self, tmpl: &mut TemplateBuffer<'_>) {
+        tmpl.write_fmt(format_args!("{}", self));
+    }
+}
@@ -0,0 +1,221 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+/*!
+[Leptos](https://docs.rs/leptos/) integration.
+
+When the `leptos` feature is enabled, every set created by [`classes`] or
+[`static_classes`] implements Leptos' `IntoClass` and `AttributeValue`
+traits, so it can be used directly as a `class=` attribute, or as the value
+of any other attribute. If [`try_as_str`][Classes::try_as_str] succeeds, the
+attribute value is a `&'static str` and nothing is allocated.
+
+Leptos already implements these traits for closures returning a type that
+implements them, so a closure returning a class set can be used as a
+reactive `class=` attribute, which will be updated whenever the signals it
+reads change:
+
+```rust
+use leptos::prelude::*;
+use semester::classes;
+
+let (active, set_active) = signal(false);
+
+let view = view! {
+    <button class=move || classes!("button", "active": active.get())>"Click"</button>
+};
+
+assert_eq!(view.to_html(), r#"<button class="button">Click</button>"#);
+# let _ = set_active;
+```
+
+[`ClassList`][crate::ClassList] implements the same traits.
+
+[`classes`]: crate::classes
+[`static_classes`]: crate::static_classes
+*/
+
+use std::borrow::Cow;
+
+use crate::{ClassList, Classes, StaticClassSet};
+
+/// Convert a class set to a `Cow<'static, str>`. If
+/// [`try_as_str`][Classes::try_as_str] succeeds, this will be borrowed, and
+/// won't allocate.
+#[must_use]
+pub fn to_cow(classes: &impl Classes) -> Cow<'static, str> {
+    match classes.try_as_str() {
+        Some(rendered) => Cow::Borrowed(rendered),
+        None => Cow::Owned(classes.to_string()),
+    }
+}
+
+/// Get the length of a class set once it's rendered, without rendering it.
+#[must_use]
+pub fn rendered_len(classes: &impl Classes) -> usize {
+    crate::rendered_len(classes.iter())
+}
+
+/// Implement Leptos' attribute traits for a type, by converting it to a
+/// `Cow<'static, str>` with `$to_cow`. Used by code generated by
+/// `semester-macro`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_leptos {
+    ($type:ty) => {
+        $crate::__impl_leptos!($type, |classes| $crate::leptos::to_cow(&classes), |classes| {
+            $crate::leptos::rendered_len(classes)
+        });
+    };
+
+    ($type:ty, |$value:ident| $to_cow:expr, |$ref:ident| $len:expr) => {
+        impl $crate::__leptos::tachys::html::class::IntoClass for $type {
+            type AsyncOutput = ::std::borrow::Cow<'static, str>;
+            type State = <::std::borrow::Cow<'static, str> as $crate::__leptos::tachys::html::class::IntoClass>::State;
+            type Cloneable = <::std::borrow::Cow<'static, str> as $crate::__leptos::tachys::html::class::IntoClass>::Cloneable;
+            type CloneableOwned = <::std::borrow::Cow<'static, str> as $crate::__leptos::tachys::html::class::IntoClass>::CloneableOwned;
+
+            #[inline]
+            fn html_len(&self) -> usize {
+                let $ref = self;
+                $len
+            }
+
+            #[inline]
+            fn to_html(self, class: &mut ::std::string::String) {
+                let $value = self;
+                $crate::__leptos::tachys::html::class::IntoClass::to_html($to_cow, class)
+            }
+
+            #[inline]
+            fn should_overwrite(&self) -> bool {
+                true
+            }
+
+            #[inline]
+            fn hydrate<const FROM_SERVER: bool>(
+                self,
+                el: &$crate::__leptos::tachys::renderer::types::Element,
+            ) -> Self::State {
+                let $value = self;
+                $crate::__leptos::tachys::html::class::IntoClass::hydrate::<FROM_SERVER>($to_cow, el)
+            }
+
+            #[inline]
+            fn build(self, el: &$crate::__leptos::tachys::renderer::types::Element) -> Self::State {
+                let $value = self;
+                $crate::__leptos::tachys::html::class::IntoClass::build($to_cow, el)
+            }
+
+            #[inline]
+            fn rebuild(self, state: &mut Self::State) {
+                let $value = self;
+                $crate::__leptos::tachys::html::class::IntoClass::rebuild($to_cow, state)
+            }
+
+            #[inline]
+            fn into_cloneable(self) -> Self::Cloneable {
+                let $value = self;
+                $crate::__leptos::tachys::html::class::IntoClass::into_cloneable($to_cow)
+            }
+
+            #[inline]
+            fn into_cloneable_owned(self) -> Self::CloneableOwned {
+                let $value = self;
+                $crate::__leptos::tachys::html::class::IntoClass::into_cloneable_owned($to_cow)
+            }
+
+            #[inline]
+            fn dry_resolve(&mut self) {}
+
+            async fn resolve(self) -> Self::AsyncOutput {
+                let $value = self;
+                $to_cow
+            }
+
+            #[inline]
+            fn reset(state: &mut Self::State) {
+                <::std::borrow::Cow<'static, str> as $crate::__leptos::tachys::html::class::IntoClass>::reset(state)
+            }
+        }
+
+        impl $crate::__leptos::tachys::html::attribute::AttributeValue for $type {
+            type AsyncOutput = ::std::borrow::Cow<'static, str>;
+            type State = <::std::borrow::Cow<'static, str> as $crate::__leptos::tachys::html::attribute::AttributeValue>::State;
+            type Cloneable = <::std::borrow::Cow<'static, str> as $crate::__leptos::tachys::html::attribute::AttributeValue>::Cloneable;
+            type CloneableOwned = <::std::borrow::Cow<'static, str> as $crate::__leptos::tachys::html::attribute::AttributeValue>::CloneableOwned;
+
+            #[inline]
+            fn html_len(&self) -> usize {
+                let $ref = self;
+                $len
+            }
+
+            #[inline]
+            fn to_html(self, key: &str, buf: &mut ::std::string::String) {
+                let $value = self;
+                $crate::__leptos::tachys::html::attribute::AttributeValue::to_html($to_cow, key, buf)
+            }
+
+            #[inline]
+            fn to_template(_key: &str, _buf: &mut ::std::string::String) {}
+
+            #[inline]
+            fn hydrate<const FROM_SERVER: bool>(
+                self,
+                key: &str,
+                el: &$crate::__leptos::tachys::renderer::types::Element,
+            ) -> Self::State {
+                let $value = self;
+                $crate::__leptos::tachys::html::attribute::AttributeValue::hydrate::<FROM_SERVER>($to_cow, key, el)
+            }
+
+            #[inline]
+            fn build(
+                self,
+                el: &$crate::__leptos::tachys::renderer::types::Element,
+                key: &str,
+            ) -> Self::State {
+                let $value = self;
+                $crate::__leptos::tachys::html::attribute::AttributeValue::build($to_cow, el, key)
+            }
+
+            #[inline]
+            fn rebuild(self, key: &str, state: &mut Self::State) {
+                let $value = self;
+                $crate::__leptos::tachys::html::attribute::AttributeValue::rebuild($to_cow, key, state)
+            }
+
+            #[inline]
+            fn into_cloneable(self) -> Self::Cloneable {
+                let $value = self;
+                $crate::__leptos::tachys::html::attribute::AttributeValue::into_cloneable($to_cow)
+            }
+
+            #[inline]
+            fn into_cloneable_owned(self) -> Self::CloneableOwned {
+                let $value = self;
+                $crate::__leptos::tachys::html::attribute::AttributeValue::into_cloneable_owned($to_cow)
+            }
+
+            #[inline]
+            fn dry_resolve(&mut self) {}
+
+            async fn resolve(self) -> Self::AsyncOutput {
+                let $value = self;
+                $to_cow
+            }
+        }
+    };
+}
+
+__impl_leptos!(StaticClassSet);
+
+__impl_leptos!(
+    ClassList,
+    |list| Cow::<'static, str>::Owned(list.to_string()),
+    |list| crate::rendered_len(list.iter())
+);
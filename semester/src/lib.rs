@@ -17,7 +17,7 @@ but they both use the same simple syntax and perform the same checks and
 guarantees:
 
 - The macro takes a list of CSS classes as input, and returns an
-`impl `[`Classes`]:
+  `impl `[`Classes`]:
 
 ```rust
 use semester::{classes, Classes as _};
@@ -60,7 +60,8 @@ and `to_string`.
 Additionally, `semester` performs several compile time correctness checks on
 your classes:
 
-- Classes must be made up of ascii printable characters:
+- Classes must not contain control characters, and must be made up of ascii
+  printable characters unless the `unicode` feature is enabled:
 
 ```compile_fail
 use semester::classes;
@@ -93,7 +94,7 @@ classes!("<injected-class>")
 ```
 
 - Classes may not duplicate. Note that `semester` can't detect mutually
-exclusive conditions, so it prevents duplicates unconditionally.
+  exclusive conditions, so it prevents duplicates unconditionally.
 
 ```compile_fail
 use semester::classes;
@@ -105,10 +106,382 @@ classes!(
 );
 ```
 
+- Conditions must be `bool`:
+
+```compile_fail
+use semester::classes;
+
+let count = 3;
+classes!("has-items": count)
+```
+
+All of the invalid and duplicate classes in a macro are reported together,
+with a suggested fix where there's an obvious one, like splitting `"a b"`
+into two entries or removing a trailing space.
+
+# Variant groups
+
+Utility frameworks like [Tailwind] use variant prefixes like `md:` and
+`hover:`, which get repetitive. Classes that share a variant can be grouped:
+
+```
+use semester::{classes, Classes as _};
+
+let wide = true;
+let classes = classes!(
+    "flex-col",
+    "md:" { "flex-row", "gap-4", "px-6": wide },
+    "dark:" { "bg-black", "hover:" { "bg-gray-800" } },
+);
+
+assert_eq!(
+    classes.render(),
+    "flex-col md:flex-row md:gap-4 md:px-6 dark:bg-black dark:hover:bg-gray-800",
+);
+```
+
+Groups are expanded at compile time, and can be nested. Classes in a group
+can have conditions, and the expanded names are checked (including for
+duplicates) like any other class. A [prefix](#prefixes) goes after the
+variants, so with a `tw-` prefix, `"md:" { "flex" }` is `md:tw-flex`.
+
+```compile_fail
+use semester::classes;
+
+classes!("md:flex", "md:" { "flex" });
+```
+
+# BEM
+
+For [BEM]-style class names, the [`bem!`] and [`static_bem!`] macros join
+block, element and modifier names at compile time, and always include the
+base class:
+
+```
+use semester::{bem, Classes as _};
+
+let (collapsed, sticky) = (true, false);
+let header = bem!("card", "header"; "collapsed": collapsed, "sticky": sticky);
+
+assert_eq!(header.render(), "card__header card__header--collapsed");
+```
+
+# Prefixes
+
+Libraries embedded in other people's pages often need a prefix on every class,
+to avoid colliding with the page's own classes. Set `prefix` in the crate's
+[configuration](#configuration), or give a prefix to a single invocation,
+before the classes:
+
+```
+use semester::{classes, Classes as _};
+
+let active = true;
+let classes = classes!(prefix = "acme-"; "btn", "active": active, ::"clearfix");
+
+assert_eq!(classes.render(), "acme-btn acme-active clearfix");
+```
+
+The prefix is applied at compile time, so it's included in the pre-rendered
+strings and in all of the checks. Classes written with a leading `::`, like
+`::"clearfix"` above, are global: they're never prefixed. A prefix in an
+invocation replaces the configured prefix; use `prefix = "";` to disable it.
+
+Duplicates are detected after the prefix is applied:
+
+```compile_fail
+use semester::classes;
+
+classes!(prefix = "acme-"; "btn", ::"acme-btn");
+```
+
+# CSS Modules
+
+`classes!` and `static_classes!` can translate class names through a [CSS
+Module], so that the rendered classes match the hashed names generated for
+your stylesheets. Name the module before the classes:
+
+```ignore
+use semester::{classes, Classes as _};
+
+let active = true;
+let classes = classes!(mod "src/button.module.css"; "button", "active": active);
+
+assert_eq!(classes.render(), "button_x7f2a active_b81c3");
+```
+
+The names are translated at compile time, using a JSON map in the format
+written by tools like [postcss-modules]:
+
+```json
+{ "button": "button_x7f2a", "active": "active_b81c3" }
+```
+
+The map is read from the module path with `.json` appended (here,
+`src/button.module.css.json`), or from the path itself if it already ends
+with `.json`. Relative paths are resolved relative to the crate being
+compiled. Classes that aren't in the map are compile errors, and the map is
+tracked by the compiler, so editing it correctly triggers a rebuild.
+
+Module names are already scoped, so they aren't given a [prefix](#prefixes).
+Global classes, written with a leading `::`, aren't looked up in the module:
+they're rendered exactly as written.
+
+# Checking against stylesheets
+
+`semester` can also check that every class is actually defined in your CSS.
+List your stylesheets in the crate's [configuration](#configuration), and
+every class passed to `classes!` or `static_classes!` must appear in a
+selector in at least one of them:
+
+```toml
+[package.metadata.semester]
+stylesheets = ["static/site.css"]
+```
+
+They can also be set with the `SEMESTER_STYLESHEETS` environment variable, as
+a list of paths separated like `PATH` (`:` on unix, `;` on windows). Relative
+paths are resolved relative to the crate being compiled.
+
+Unknown classes are reported as compile errors, with a suggestion if there's a
+similarly named class in the stylesheets:
+
+```text
+error: class `btn-primray` is not defined in any stylesheet; did you mean `btn-primary`?
+```
+
+Every class is checked, including classes whose condition is a literal
+`false`. The stylesheets are tracked by the compiler, so editing them
+correctly triggers a rebuild.
+
+# Tailwind conflicts
+
+`classes!("p-2", "p-4": big)` is legal, but almost always a bug: when `big`
+is true, both classes are active, and the order of the rules in the
+stylesheet decides which padding wins, not the order in the macro. Set
+`tailwind-conflicts = "warn"` (or `"deny"`) in the crate's
+[configuration](#configuration) to have `semester` report [Tailwind]
+utilities that conflict with each other:
+
+```text
+warning: use of deprecated constant `main::_::semester_warning`: Tailwind class `p-4` conflicts with `p-2` (both set padding); when both are active, the stylesheet decides which one applies, not the order here
+```
+
+The lint knows the most common utility groups, including ones that overlap
+(like `p-4` and `px-2`), and takes variants and the `!` modifier into
+account, so `p-2` and `md:p-4` don't conflict. Conflicting classes are allowed
+if their conditions are obviously exclusive, like `"mt-2": big, "mt-4": !big`.
+Classes the lint doesn't recognize never conflict. Warnings are reported as
+deprecation warnings, since that's the only way for a macro to emit warnings
+on stable Rust.
+
+# Merging classes
+
+Components often have default classes that their caller should be able to
+override, like a default padding. [`Classes::merge`] combines a set of
+classes with overriding classes, from another set or a runtime string, and
+drops the classes that the overrides conflict with, like the JavaScript
+`tailwind-merge` library:
+
+```rust
+use semester::{classes, Classes as _};
+
+let base = classes!("card", "p-4", "text-sm");
+
+assert_eq!(base.merge("p-8 shadow").to_string(), "card text-sm p-8 shadow");
+```
+
+Conflicts are decided by the same table of [Tailwind] utility groups as the
+lint above. [`Classes::merge_with`] takes a custom [`MergeTable`], which can
+add groups for other class names, or set the Tailwind prefix. Merging doesn't
+allocate: the merged classes are computed when they're iterated or
+displayed.
+
+# Class manifests
+
+Tools like [PurgeCSS] and [Tailwind] need to know which classes are used, but
+can't reliably find them inside of Rust macros. If `manifest-dir` is
+[configured](#configuration), or the `SEMESTER_MANIFEST_DIR` environment
+variable is set, `semester` writes every class used by the crate being
+compiled to `<crate name>.json` in that directory, or `<crate name>.bin.json`
+for a binary, since a package's library and binary can have the same crate
+name. The manifest is a sorted JSON array:
+
+```json
+[
+  "active",
+  "btn",
+  "btn-primary"
+]
+```
+
+The manifest includes every class that appears in `classes!` or
+`static_classes!`, whether or not it's conditional, so it can be used
+directly as a safelist. Relative paths are resolved relative to the crate
+being compiled. To write the manifest to `OUT_DIR` instead, set the variable
+from a build script:
+
+```no_run
+println!(
+    "cargo:rustc-env=SEMESTER_MANIFEST_DIR={}",
+    std::env::var("OUT_DIR").unwrap(),
+);
+```
+
+The manifest is only rewritten when the crate is recompiled. It isn't
+written when the macros are expanded by rust-analyzer, whose long-running
+process would otherwise keep classes that have since been removed.
+
+# Mangling
+
+To save bytes in production HTML, `semester` can replace every class name
+with a short, opaque mangled name in release builds. Set the
+`SEMESTER_MANGLE` environment variable to the path of a mangling table, which
+is a JSON file mapping each original class name to its mangled name:
+
+```json
+{
+  "btn": "zh7x",
+  "btn-primary": "ovgf"
+}
+```
+
+`semester` creates the table if it doesn't exist, and adds new classes to it
+as they're encountered. Your CSS build step should apply the same renaming to
+your stylesheets. Mangled names are derived from a hash of the original name,
+so they're stable across builds, and every crate that shares a table is
+guaranteed to get distinct names for distinct classes (in the rare case of a
+hash collision, the newer class gets a longer name). To share a table across
+a workspace, set it in `.cargo/config.toml` with an absolute path:
+
+```toml
+[env]
+SEMESTER_MANGLE = { value = "target/semester-mangle.json", relative = true }
+```
+
+By default, mangling only happens when `semester-macro` is compiled without
+debug assertions. With cargo's default profiles that's the same as the crates
+that use it, so debug builds keep the original names, but a `build-override`
+in the profile can compile the macros differently. Set `mangle = "always"` or
+`mangle = "never"` in the [configuration](#configuration), or the
+`SEMESTER_MANGLE_MODE` environment variable, to choose explicitly. Checks
+against stylesheets and class manifests always use the original names.
+
+Only the macros know the original names, so runtime APIs that match class
+names against strings see the mangled names instead:
+[`merge`][Classes::merge] doesn't recognize mangled utilities, and extra
+classes passed to [`with_extra`][Classes::with_extra] or checked by a
+[`ClassFilter`] have to be mangled names to match. Don't combine these APIs
+with mangling.
+
+# Configuration
+
+`semester` reads crate-wide configuration from the
+`[package.metadata.semester]` table in your crate's `Cargo.toml`, so that
+every macro invocation in the crate uses the same settings:
+
+```toml
+[package.metadata.semester]
+# Stylesheets to check class names against
+stylesheets = ["static/site.css"]
+# Directory to write the class manifest to
+manifest-dir = "target/classes"
+# Mangling table, used in release builds
+mangle-table = "target/semester-mangle.json"
+# When to mangle: "release" (the default), "always" or "never"
+mangle = "release"
+# Prefix for every class name
+prefix = "acme-"
+# Naming convention ("kebab", "bem" or "suit") or regex that every class
+# name must follow
+naming = "kebab"
+# Limit on the number of conditions in `static_classes!` (unlimited by
+# default)
+max-static-conditions = 8
+# Whether failed stylesheet and naming checks are errors ("deny", the
+# default), warnings ("warn") or ignored ("allow")
+strictness = "warn"
+# Lint for conflicting Tailwind utilities ("allow" by default)
+tailwind-conflicts = "warn"
+# Require class names to be CSS identifiers (false by default)
+strict-idents = true
+```
+
+Relative paths are resolved relative to the crate. The `SEMESTER_STYLESHEETS`,
+`SEMESTER_MANIFEST_DIR`, `SEMESTER_MANGLE` and `SEMESTER_MANGLE_MODE`
+environment variables take precedence over the corresponding settings. `strictness` only affects the
+optional checks; invalid class names, like ones with whitespace, are always
+errors.
+
+`naming` can also be a list of rules, or a table of named rules, in which
+case every class name has to follow at least one of them:
+
+```toml
+[package.metadata.semester.naming]
+utilities = "kebab"
+hooks = "^js-[a-z][a-z0-9-]*$"
+```
+
+Naming rules are checked against each class name as it's written in the
+macro, before any prefix, variants or CSS module are applied. Violations are
+reported with the name of the rule, as errors or warnings depending on
+`strictness`.
+
+By default, class names can contain any printable ASCII character except
+`<>&'"` (or, with the `unicode` feature, any character except those,
+whitespace and control characters), so names like `w-1/2` and `md:p-4` are
+allowed, even though they have to be escaped in a selector. With `strict-idents = true`, every class
+name, including its prefix and variants, must be a CSS identifier: it can
+only contain letters, digits, `-` and `_`, and it can't start with a digit
+or with a hyphen followed by a digit, or be `-` or `--` on its own.
+Violations are always errors.
+
+# Features
+
+- `std` (default): enables `alloc` and implements `std::error::Error` for
+  error types.
+- `alloc`: enables methods that allocate, like [`Classes::render`], and the
+  runtime [`ClassList`] type.
+- `serde`: implements `Serialize` and `Deserialize` for class sets. See the
+  [`serde`][crate::serde] module for details.
+- `ufmt`: implements [`ufmt::uDisplay`](https://docs.rs/ufmt/) for class
+  sets, for use on embedded targets where `core::fmt` is too large.
+- `yew`: allows class sets to be used directly as `class=` attributes and
+  props in [Yew]. See the [`yew`][crate::yew] module for details.
+- `leptos`: allows class sets, and closures returning them, to be used
+  directly as attributes in [Leptos]. See the [`leptos`][crate::leptos]
+  module for details.
+- `dioxus`: allows class sets to be used directly as attribute values in
+  [Dioxus]. See the [`dioxus`][crate::dioxus] module for details.
+- `horrorshow`: allows class sets to be rendered directly in [horrorshow]
+  templates. See the [`horrorshow`][crate::horrorshow] module for details.
+- `maud`: allows class sets to be rendered directly in [maud] templates. See
+  the [`maud`][crate::maud] module for details.
+- `minijinja`: allows class sets to be used as [MiniJinja] values, and adds
+  a `classes(...)` template function. See the [`minijinja`][crate::minijinja]
+  module for details.
+- `tera`: allows class sets to be used as [Tera] values, and adds a
+  `classes(...)` template function. See the [`tera`][crate::tera] module for
+  details.
+- `unicode`: allows non-ASCII class names, like `"botón"`, in the macros and
+  at runtime (see [`validate_class_name`]). Whitespace (including Unicode
+  whitespace), control characters and `<>&'"` are still rejected, so class
+  names never need to be escaped in HTML.
+
 [React]: https://reactjs.org/
 [classnames]: https://jedwatson.github.io/classnames/
 [yew]: https://docs.rs/yew/
+[leptos]: https://docs.rs/leptos/
+[dioxus]: https://docs.rs/dioxus/
 [horrorshow]: https://docs.rs/horrorshow/
+[maud]: https://docs.rs/maud/
+[minijinja]: https://docs.rs/minijinja/
+[tera]: https://docs.rs/tera/
+[purgecss]: https://purgecss.com/
+[tailwind]: https://tailwindcss.com/
+[bem]: https://getbem.com/
+[css module]: https://github.com/css-modules/css-modules
+[postcss-modules]: https://github.com/madyankin/postcss-modules
 */
 
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -124,8 +497,91 @@ use core::{
 #[cfg(feature = "alloc")]
 use alloc::borrow::Cow;
 
+mod attr;
+#[cfg(feature = "dioxus")]
+#[cfg_attr(docsrs, doc(cfg(feature = "dioxus")))]
+pub mod dioxus;
+mod extra;
+#[cfg(feature = "alloc")]
+mod filter;
+#[cfg(feature = "horrorshow")]
+#[cfg_attr(docsrs, doc(cfg(feature = "horrorshow")))]
+pub mod horrorshow;
+#[cfg(feature = "leptos")]
+#[cfg_attr(docsrs, doc(cfg(feature = "leptos")))]
+pub mod leptos;
+#[cfg(feature = "alloc")]
+mod list;
+#[cfg(feature = "maud")]
+#[cfg_attr(docsrs, doc(cfg(feature = "maud")))]
+pub mod maud;
+mod merge;
+#[cfg(feature = "minijinja")]
+#[cfg_attr(docsrs, doc(cfg(feature = "minijinja")))]
+pub mod minijinja;
+mod name;
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub mod serde;
+mod tailwind;
+#[cfg(feature = "tera")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tera")))]
+pub mod tera;
+#[cfg(feature = "ufmt")]
+mod ufmt;
+#[cfg(feature = "yew")]
+#[cfg_attr(docsrs, doc(cfg(feature = "yew")))]
+pub mod yew;
+
+pub use attr::Attr;
+pub use extra::WithExtra;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use filter::{ClassFilter, FilterError, Filtered, Rejected, Rejection};
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use list::ClassList;
+pub use merge::{ClassSource, MergeGroup, MergeTable, Merged};
+pub use name::{validate_class_name, ClassNameError};
+
+#[doc(hidden)]
+pub use semester_macro::{bem_impl, classes_impl, static_bem_impl, static_classes_impl};
+
+#[cfg(feature = "serde")]
 #[doc(hidden)]
-pub use semester_macro::{classes_impl, static_classes_impl};
+pub use ::serde as __serde;
+
+#[cfg(feature = "ufmt")]
+#[doc(hidden)]
+pub use ::ufmt as __ufmt;
+
+#[cfg(feature = "yew")]
+#[doc(hidden)]
+pub use ::yew as __yew;
+
+#[cfg(feature = "leptos")]
+#[doc(hidden)]
+pub use ::leptos as __leptos;
+
+#[cfg(feature = "dioxus")]
+#[doc(hidden)]
+pub use ::dioxus_core as __dioxus_core;
+
+#[cfg(feature = "horrorshow")]
+#[doc(hidden)]
+pub use ::horrorshow as __horrorshow;
+
+#[cfg(feature = "maud")]
+#[doc(hidden)]
+pub use ::maud as __maud;
+
+#[cfg(feature = "minijinja")]
+#[doc(hidden)]
+pub use ::minijinja as __minijinja;
+
+#[cfg(feature = "tera")]
+#[doc(hidden)]
+pub use ::tera as __tera;
 
 /**
 Create a set of classes dynamically.
@@ -164,11 +620,9 @@ assert_eq!(get_classes(true, true).render(), "class1 class2 both");
 */
 #[macro_export]
 macro_rules! classes {
-    ($($( $class:literal $(: $condition:expr)? ),+ $(,)?)?) => {
-        ::semester::classes_impl!(
-            $($( $class $(: $condition)? ,)+)?
-        )
-    }
+    ($($input:tt)*) => {
+        ::semester::classes_impl!($($input)*)
+    };
 }
 
 /**
@@ -221,11 +675,70 @@ assert_eq!(
 */
 #[macro_export]
 macro_rules! static_classes {
-    ($($( $class:literal $(: $condition:expr)? ),+ $(,)?)?) => {
-        ::semester::static_classes_impl!(
-            $($( $class $(: $condition)? ,)+)?
-        )
-    }
+    ($($input:tt)*) => {
+        ::semester::static_classes_impl!($($input)*)
+    };
+}
+
+/**
+Create a set of classes from [BEM] block, element and modifier names.
+
+`bem!("block", "element"; "modifier": condition, ...)` creates the same set
+of classes as [`classes`] would for
+`classes!("block__element", "block__element--modifier": condition, ...)`.
+The names are joined at compile time, and the results are checked like any
+other class names. The element is optional, and the `block` or
+`block__element` class is always included.
+
+[BEM]: https://getbem.com/
+
+# Example
+
+```rust
+use semester::{bem, Classes as _};
+
+let (collapsed, sticky) = (true, false);
+
+let header = bem!("card", "header"; "collapsed": collapsed, "sticky": sticky);
+assert_eq!(header.render(), "card__header card__header--collapsed");
+
+let card = bem!("card"; "wide": !collapsed);
+assert_eq!(card.render(), "card");
+```
+*/
+#[macro_export]
+macro_rules! bem {
+    ($($input:tt)*) => {
+        ::semester::bem_impl!($($input)*)
+    };
+}
+
+/**
+Create a set of classes statically from [BEM] block, element and modifier
+names.
+
+This is the [`bem`] equivalent of [`static_classes`]: it pre-computes every
+possible combination of modifiers, and returns an
+`impl `[`StaticClasses`].
+
+[BEM]: https://getbem.com/
+
+# Example
+
+```rust
+use semester::{static_bem, StaticClasses as _};
+
+let active = true;
+let button = static_bem!("button"; "primary", "active": active);
+
+assert_eq!(button.as_str(), "button button--primary button--active");
+```
+*/
+#[macro_export]
+macro_rules! static_bem {
+    ($($input:tt)*) => {
+        ::semester::static_bem_impl!($($input)*)
+    };
 }
 
 /**
@@ -264,6 +777,83 @@ pub trait Classes:
     /// Get the number of enabled classes in this set.
     #[must_use]
     fn len(&self) -> usize;
+
+    /// Get every class that this set could possibly contain, regardless of
+    /// which ones are currently enabled, in declaration order. Classes with
+    /// conditions that are known at compile time to be false are omitted.
+    ///
+    /// The sets created by the macros always know their universe. Other
+    /// implementations of this trait return an empty slice unless they
+    /// override this method.
+    #[inline]
+    #[must_use]
+    fn universe(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Get an HTML `class` attribute for this set. When displayed, it renders
+    /// as ` class="..."` (with a leading space), or as nothing at all if this
+    /// set is empty. See [`Attr`] for details.
+    #[inline]
+    #[must_use]
+    fn attr(&self) -> Attr<'static, Self> {
+        self.attr_named("class")
+    }
+
+    /// Get an HTML attribute with a custom name for this set, like
+    /// [`attr`][Self::attr]. The name is written verbatim.
+    #[inline]
+    #[must_use]
+    fn attr_named<'a>(&self, name: &'a str) -> Attr<'a, Self> {
+        Attr::new(name, *self)
+    }
+
+    /// Combine this set with extra classes from a whitespace-separated
+    /// runtime string, such as a `class` prop passed to a component. Invalid
+    /// extra classes, and extra classes that duplicate classes already in the
+    /// set, are dropped. See [`WithExtra`] for details.
+    #[inline]
+    #[must_use]
+    fn with_extra<'a>(&self, extra: &'a str) -> WithExtra<Self, &'a str> {
+        WithExtra::new(*self, extra)
+    }
+
+    /// Combine this set with extra classes from an owned string, like
+    /// [`with_extra`][Self::with_extra].
+    #[inline]
+    #[must_use]
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    fn with_extra_owned(
+        &self,
+        extra: alloc::string::String,
+    ) -> WithExtra<Self, alloc::string::String> {
+        WithExtra::new(*self, extra)
+    }
+
+    /// Merge overriding classes into this set, resolving conflicts between
+    /// [Tailwind](https://tailwindcss.com/) utilities: a class from this set
+    /// is dropped if an overriding class conflicts with it, like `p-4` and
+    /// `p-8`. The overrides can be another set of classes or a
+    /// whitespace-separated runtime string. See [`Merged`] for details.
+    #[inline]
+    #[must_use]
+    fn merge<S: ClassSource>(&self, overrides: S) -> Merged<'static, Self, S> {
+        Merged::new(*self, overrides, &MergeTable::TAILWIND)
+    }
+
+    /// Merge overriding classes into this set, like [`merge`][Self::merge],
+    /// but with a custom table of conflicting groups. See [`MergeTable`] for
+    /// details.
+    #[inline]
+    #[must_use]
+    fn merge_with<'t, S: ClassSource>(
+        &self,
+        overrides: S,
+        table: &'t MergeTable<'t>,
+    ) -> Merged<'t, Self, S> {
+        Merged::new(*self, overrides, table)
+    }
 }
 
 /**
@@ -281,35 +871,45 @@ pub trait StaticClasses:
     /// Get a slice containing the full set of classes
     #[must_use]
     fn class_set(&self) -> &'static [&'static str];
+
+    /// Used to implement [`Classes::universe`]. Types whose set of classes
+    /// is fixed don't need to override this.
+    #[doc(hidden)]
+    #[inline]
+    #[must_use]
+    fn static_universe(&self) -> &'static [&'static str] {
+        self.class_set()
+    }
 }
 
 impl<T: StaticClasses> Classes for T {
     type Iter = core::iter::Copied<core::slice::Iter<'static, &'static str>>;
 
     #[inline]
-    #[must_use]
     #[cfg(feature = "alloc")]
     fn render(&self) -> Cow<'static, str> {
         Cow::Borrowed(self.as_str())
     }
 
     #[inline]
-    #[must_use]
     fn try_as_str(&self) -> Option<&'static str> {
         Some(self.as_str())
     }
 
     #[inline]
-    #[must_use]
     fn iter(&self) -> Self::Iter {
         self.class_set().iter().copied()
     }
 
     #[inline]
-    #[must_use]
     fn len(&self) -> usize {
         self.class_set().len()
     }
+
+    #[inline]
+    fn universe(&self) -> &'static [&'static str] {
+        self.static_universe()
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -317,6 +917,7 @@ impl<T: StaticClasses> Classes for T {
 pub struct StaticClassSet {
     class_set: &'static [&'static str],
     rendered: &'static str,
+    universe: &'static [&'static str],
 }
 
 impl StaticClassSet {
@@ -327,32 +928,50 @@ impl StaticClassSet {
     ///
     /// # Safety
     ///
-    /// - class_set must have 0 or more nonempty strings that contain only
-    ///   ascii printables and do not contain < > ' " &
+    /// - class_set must have 0 or more nonempty strings that do not contain
+    ///   whitespace, control characters or < > ' " &, and that are ascii
+    ///   unless the `unicode` feature is enabled
     /// - class_set must not have duplicates
     /// - rendered must be equivalent to class_set.join(" ")
-
     #[inline]
     pub unsafe fn new(class_set: &'static [&'static str], rendered: &'static str) -> Self {
         Self {
             class_set,
             rendered,
+            universe: class_set,
         }
     }
+
+    /// Set the universe of this `StaticClassSet`, the classes it could have
+    /// contained. Like [`new`][Self::new], this should *only* be called by
+    /// code generated by `semester-macro`.
+    ///
+    /// # Safety
+    ///
+    /// - universe must follow the same rules as class_set, and class_set
+    ///   must be a subsequence of it
+    #[inline]
+    #[must_use]
+    pub unsafe fn with_universe(self, universe: &'static [&'static str]) -> Self {
+        Self { universe, ..self }
+    }
 }
 
 impl StaticClasses for StaticClassSet {
     #[inline]
-    #[must_use]
     fn as_str(&self) -> &'static str {
         self.rendered
     }
 
     #[inline]
-    #[must_use]
     fn class_set(&self) -> &'static [&'static str] {
         self.class_set
     }
+
+    #[inline]
+    fn static_universe(&self) -> &'static [&'static str] {
+        self.universe
+    }
 }
 
 impl Display for StaticClassSet {
@@ -361,16 +980,254 @@ impl Display for StaticClassSet {
     }
 }
 
+/// Extra traits implemented by every set created by the macros when the
+/// `serde` feature is enabled. Used to keep them visible through
+/// [`erase_classes`] and [`erase_static_classes`].
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+pub trait MaybeSerde: ::serde::Serialize + ::serde::de::DeserializeOwned {}
+
+#[cfg(feature = "serde")]
+impl<T: ::serde::Serialize + ::serde::de::DeserializeOwned> MaybeSerde for T {}
+
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+pub trait MaybeSerde {}
+
+#[cfg(not(feature = "serde"))]
+impl<T> MaybeSerde for T {}
+
+/// Extra traits implemented by every set created by the macros when the
+/// `ufmt` feature is enabled.
+#[cfg(feature = "ufmt")]
+#[doc(hidden)]
+pub trait MaybeUfmt: ::ufmt::uDisplay {}
+
+#[cfg(feature = "ufmt")]
+impl<T: ::ufmt::uDisplay> MaybeUfmt for T {}
+
+#[cfg(not(feature = "ufmt"))]
+#[doc(hidden)]
+pub trait MaybeUfmt {}
+
+#[cfg(not(feature = "ufmt"))]
+impl<T> MaybeUfmt for T {}
+
+/// Extra traits implemented by every set created by the macros when the
+/// `yew` feature is enabled.
+#[cfg(feature = "yew")]
+#[doc(hidden)]
+pub trait MaybeYew:
+    Into<::yew::html::Classes>
+    + ::yew::html::IntoPropValue<::yew::html::Classes>
+    + ::yew::html::IntoPropValue<::yew::AttrValue>
+    + ::yew::html::IntoPropValue<Option<::yew::AttrValue>>
+    + ::yew::html::ToHtml
+{
+}
+
+#[cfg(feature = "yew")]
+impl<T> MaybeYew for T where
+    T: Into<::yew::html::Classes>
+        + ::yew::html::IntoPropValue<::yew::html::Classes>
+        + ::yew::html::IntoPropValue<::yew::AttrValue>
+        + ::yew::html::IntoPropValue<Option<::yew::AttrValue>>
+        + ::yew::html::ToHtml
+{
+}
+
+#[cfg(not(feature = "yew"))]
+#[doc(hidden)]
+pub trait MaybeYew {}
+
+#[cfg(not(feature = "yew"))]
+impl<T> MaybeYew for T {}
+
+/// Extra traits implemented by every set created by the macros when the
+/// `leptos` feature is enabled.
+#[cfg(feature = "leptos")]
+#[doc(hidden)]
+pub trait MaybeLeptos:
+    ::leptos::tachys::html::class::IntoClass + ::leptos::tachys::html::attribute::AttributeValue
+{
+}
+
+#[cfg(feature = "leptos")]
+impl<T> MaybeLeptos for T where
+    T: ::leptos::tachys::html::class::IntoClass + ::leptos::tachys::html::attribute::AttributeValue
+{
+}
+
+#[cfg(not(feature = "leptos"))]
+#[doc(hidden)]
+pub trait MaybeLeptos {}
+
+#[cfg(not(feature = "leptos"))]
+impl<T> MaybeLeptos for T {}
+
+/// Extra traits implemented by every set created by the macros when the
+/// `dioxus` feature is enabled.
+#[cfg(feature = "dioxus")]
+#[doc(hidden)]
+pub trait MaybeDioxus: ::dioxus_core::IntoAttributeValue {}
+
+#[cfg(feature = "dioxus")]
+impl<T: ::dioxus_core::IntoAttributeValue> MaybeDioxus for T {}
+
+#[cfg(not(feature = "dioxus"))]
+#[doc(hidden)]
+pub trait MaybeDioxus {}
+
+#[cfg(not(feature = "dioxus"))]
+impl<T> MaybeDioxus for T {}
+
+/// Extra traits implemented by every set created by the macros when the
+/// `horrorshow` feature is enabled.
+#[cfg(feature = "horrorshow")]
+#[doc(hidden)]
+pub trait MaybeHorrorshow: ::horrorshow::Render {}
+
+#[cfg(feature = "horrorshow")]
+impl<T: ::horrorshow::Render> MaybeHorrorshow for T {}
+
+#[cfg(not(feature = "horrorshow"))]
+#[doc(hidden)]
+pub trait MaybeHorrorshow {}
+
+#[cfg(not(feature = "horrorshow"))]
+impl<T> MaybeHorrorshow for T {}
+
+/// Extra traits implemented by every set created by the macros when the
+/// `maud` feature is enabled.
+#[cfg(feature = "maud")]
+#[doc(hidden)]
+pub trait MaybeMaud: ::maud::Render {}
+
+#[cfg(feature = "maud")]
+impl<T: ::maud::Render> MaybeMaud for T {}
+
+#[cfg(not(feature = "maud"))]
+#[doc(hidden)]
+pub trait MaybeMaud {}
+
+#[cfg(not(feature = "maud"))]
+impl<T> MaybeMaud for T {}
+
+/// Extra traits implemented by every set created by the macros when the
+/// `minijinja` feature is enabled.
+#[cfg(feature = "minijinja")]
+#[doc(hidden)]
+pub trait MaybeMinijinja: Into<::minijinja::Value> {}
+
+#[cfg(feature = "minijinja")]
+impl<T: Into<::minijinja::Value>> MaybeMinijinja for T {}
+
+#[cfg(not(feature = "minijinja"))]
+#[doc(hidden)]
+pub trait MaybeMinijinja {}
+
+#[cfg(not(feature = "minijinja"))]
+impl<T> MaybeMinijinja for T {}
+
+/// Extra traits implemented by every set created by the macros when the
+/// `tera` feature is enabled.
+#[cfg(feature = "tera")]
+#[doc(hidden)]
+pub trait MaybeTera: Into<::tera::Value> {}
+
+#[cfg(feature = "tera")]
+impl<T: Into<::tera::Value>> MaybeTera for T {}
+
+#[cfg(not(feature = "tera"))]
+#[doc(hidden)]
+pub trait MaybeTera {}
+
+#[cfg(not(feature = "tera"))]
+impl<T> MaybeTera for T {}
+
+/// All the extra traits implemented by every set created by the macros,
+/// depending on which features are enabled.
+#[doc(hidden)]
+pub trait MaybeIntegrations:
+    MaybeSerde
+    + MaybeUfmt
+    + MaybeYew
+    + MaybeLeptos
+    + MaybeDioxus
+    + MaybeHorrorshow
+    + MaybeMaud
+    + MaybeMinijinja
+    + MaybeTera
+{
+}
+
+impl<T> MaybeIntegrations for T where
+    T: MaybeSerde
+        + MaybeUfmt
+        + MaybeYew
+        + MaybeLeptos
+        + MaybeDioxus
+        + MaybeHorrorshow
+        + MaybeMaud
+        + MaybeMinijinja
+        + MaybeTera
+{
+}
+
 #[doc(hidden)]
 #[inline(always)]
 #[must_use = "classes objects are inert unless used"]
-pub fn erase_classes<T: Classes>(classes: T) -> impl Classes {
+pub fn erase_classes<T: Classes + MaybeIntegrations>(
+    classes: T,
+) -> impl Classes + MaybeIntegrations {
     classes
 }
 
 #[doc(hidden)]
 #[inline(always)]
 #[must_use = "classes objects are inert unless used"]
-pub fn erase_static_classes<T: StaticClasses>(classes: T) -> impl StaticClasses + Classes {
+pub fn erase_static_classes<T: StaticClasses + MaybeIntegrations>(
+    classes: T,
+) -> impl StaticClasses + Classes + MaybeIntegrations {
     classes
 }
+
+/// Implemented only for `bool`, so that a class condition of the wrong type
+/// is reported at the condition, with an explanation, rather than as a type
+/// error somewhere in the generated code.
+#[doc(hidden)]
+#[diagnostic::on_unimplemented(
+    message = "class conditions must be `bool`, not `{Self}`",
+    label = "expected `bool`",
+    note = "use a comparison or a method that returns `bool`, like `count > 0` or `option.is_some()`"
+)]
+pub trait Condition {
+    fn into_bool(self) -> bool;
+}
+
+impl Condition for bool {
+    #[inline(always)]
+    fn into_bool(self) -> bool {
+        self
+    }
+}
+
+#[doc(hidden)]
+#[inline(always)]
+#[must_use]
+pub fn condition(condition: impl Condition) -> bool {
+    condition.into_bool()
+}
+
+/// Get the length of some classes once they're joined with spaces, without
+/// joining them. Used to size buffers for the framework integrations.
+#[cfg(any(feature = "leptos", feature = "dioxus"))]
+fn rendered_len<'a>(classes: impl IntoIterator<Item = &'a str>) -> usize {
+    let (count, len) = classes
+        .into_iter()
+        .fold((0, 0), |(count, len): (usize, usize), class| {
+            (count + 1, len + class.len())
+        });
+
+    len + count.saturating_sub(1)
+}
@@ -0,0 +1,156 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use core::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+use alloc::{borrow::Cow, vec::Vec};
+
+use crate::{validate_class_name, Attr, ClassNameError, Classes};
+
+/**
+A `ClassList` is a set of classes computed at runtime, such as classes that
+arrive from configuration or from a caller as a string.
+
+Unlike the sets created by the macros, a `ClassList` can't be checked at
+compile time, so every class is validated with the same rules as
+[`validate_class_name`] as it's added. Classes are kept in insertion order and
+duplicates are silently dropped.
+
+# Example
+
+```rust
+use semester::{classes, ClassList};
+
+let mut list: ClassList = "card card-active".parse().unwrap();
+assert!(list.insert("shadow").unwrap());
+assert!(!list.insert("card").unwrap());
+assert!(list.insert("card header").is_err());
+
+assert_eq!(list.to_string(), "card card-active shadow");
+
+let list = ClassList::from(classes!("a", "b": true, "c": false));
+assert_eq!(list.len(), 2);
+```
+*/
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ClassList {
+    classes: Vec<Cow<'static, str>>,
+}
+
+impl ClassList {
+    /// Create a new, empty `ClassList`.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            classes: Vec::new(),
+        }
+    }
+
+    /// Parse a whitespace-separated list of classes. Fails if any of the
+    /// classes is invalid.
+    pub fn parse(classes: &str) -> Result<Self, ClassNameError> {
+        let mut list = Self::new();
+
+        classes.split_whitespace().try_for_each(|class| {
+            list.insert(Cow::Owned(class.into()))?;
+            Ok(())
+        })?;
+
+        Ok(list)
+    }
+
+    /// Add a class to the end of this list. Returns `Ok(false)` if the class
+    /// was already present, or an error if the class isn't a valid class
+    /// name.
+    pub fn insert(&mut self, class: impl Into<Cow<'static, str>>) -> Result<bool, ClassNameError> {
+        let class = class.into();
+        validate_class_name(&class)?;
+
+        Ok(match self.contains(&class) {
+            true => false,
+            false => {
+                self.classes.push(class);
+                true
+            }
+        })
+    }
+
+    /// Check if this list contains the given class.
+    #[must_use]
+    pub fn contains(&self, class: &str) -> bool {
+        self.classes.iter().any(|existing| existing == class)
+    }
+
+    /// Get an iterator over all the classes in this list.
+    #[inline]
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &str> + ExactSizeIterator + '_ {
+        self.classes.iter().map(|class| class.as_ref())
+    }
+
+    /// Get the number of classes in this list.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.classes.len()
+    }
+
+    /// Check if this list has no classes.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.classes.is_empty()
+    }
+
+    /// Get an HTML `class` attribute for this list, like [`Classes::attr`].
+    /// Unlike the macro class sets, the classes are HTML-escaped as they're
+    /// written.
+    #[inline]
+    #[must_use]
+    pub fn attr(&self) -> Attr<'static, &Self> {
+        self.attr_named("class")
+    }
+
+    /// Get an HTML attribute with a custom name for this list, like
+    /// [`Classes::attr_named`].
+    #[inline]
+    #[must_use]
+    pub fn attr_named<'a>(&self, name: &'a str) -> Attr<'a, &Self> {
+        Attr::new(name, self)
+    }
+}
+
+impl Display for ClassList {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.iter().enumerate().try_for_each(|(idx, class)| {
+            if idx > 0 {
+                f.write_str(" ")?;
+            }
+            f.write_str(class)
+        })
+    }
+}
+
+impl FromStr for ClassList {
+    type Err = ClassNameError;
+
+    #[inline]
+    fn from_str(classes: &str) -> Result<Self, Self::Err> {
+        Self::parse(classes)
+    }
+}
+
+impl<C: Classes> From<C> for ClassList {
+    fn from(classes: C) -> Self {
+        // Classes from the macros are already validated and unique
+        Self {
+            classes: classes.iter().map(Cow::Borrowed).collect(),
+        }
+    }
+}
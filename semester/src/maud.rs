@@ -0,0 +1,102 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+/*!
+[maud](https://docs.rs/maud/) integration.
+
+When the `maud` feature is enabled, every set created by [`classes`] or
+[`static_classes`] implements `maud::Render`, so it can be spliced directly
+into a template, including as an attribute value. The classes are written
+straight into the output buffer; the classes produced by the macros are
+checked at compile time for HTML unsafe characters, so no escaping is done
+for them.
+
+maud always renders an attribute, even if its value is empty. Use
+[`optional`] with maud's optional attribute syntax to omit the attribute
+entirely when there are no classes:
+
+```rust
+use maud::html;
+use semester::{classes, maud::optional};
+
+let active = false;
+
+let markup = html! {
+    button class=(classes!("button", "active": active)) { "Click" }
+    span class=[optional(classes!("active": active))] { "Label" }
+};
+
+assert_eq!(
+    markup.into_string(),
+    r#"<button class="button">Click</button><span>Label</span>"#,
+);
+```
+
+[`ClassList`][crate::ClassList] also implements `Render`. Its classes come
+from runtime strings, so they are escaped.
+
+[`classes`]: crate::classes
+[`static_classes`]: crate::static_classes
+*/
+
+use ::maud::Render;
+
+#[doc(hidden)]
+pub use alloc::string::String;
+
+use crate::{ClassList, Classes, StaticClassSet};
+
+/// Write a set of classes directly into a maud output buffer, without
+/// escaping.
+pub fn render_to(classes: &impl Classes, buffer: &mut String) {
+    classes.iter().enumerate().for_each(|(idx, class)| {
+        if idx > 0 {
+            buffer.push(' ');
+        }
+        buffer.push_str(class);
+    });
+}
+
+/// Convert a set of classes into an `Option`, which is `None` if the set is
+/// empty. When used with maud's optional attribute syntax
+/// (`class=[optional(classes)]`), this omits the attribute entirely for an
+/// empty set.
+#[inline]
+#[must_use]
+pub fn optional<C: Classes>(classes: C) -> Option<C> {
+    match classes.len() {
+        0 => None,
+        _ => Some(classes),
+    }
+}
+
+/// Implement `maud::Render` for a `Classes` type. Used by code generated by
+/// `semester-macro`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_maud {
+    ($type:ty) => {
+        impl $crate::__maud::Render for $type {
+            #[inline]
+            fn render_to(&self, buffer: &mut $crate::maud::String) {
+                $crate::maud::render_to(self, buffer)
+            }
+        }
+    };
+}
+
+__impl_maud!(StaticClassSet);
+
+impl Render for ClassList {
+    fn render_to(&self, buffer: &mut String) {
+        self.iter().enumerate().for_each(|(idx, class)| {
+            if idx > 0 {
+                buffer.push(' ');
+            }
+            class.render_to(buffer);
+        });
+    }
+}
@@ -0,0 +1,428 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use core::fmt::{self, Display, Formatter};
+
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, string::String};
+
+use crate::{tailwind, validate_class_name, Classes};
+
+/**
+Something that can be merged into a set of classes with [`Classes::merge`].
+This is implemented for every [`Classes`] type, and for whitespace-separated
+runtime strings, such as a `class` prop passed to a component. Strings are
+split on whitespace, and anything that isn't a valid class name (see
+[`validate_class_name`]) is dropped.
+*/
+pub trait ClassSource {
+    /// Get an iterator over the classes from this source, in order.
+    fn classes(&self) -> impl Iterator<Item = &str> + '_;
+}
+
+impl<C: Classes> ClassSource for C {
+    #[inline]
+    fn classes(&self) -> impl Iterator<Item = &str> + '_ {
+        self.iter().map(|class| class as &str)
+    }
+}
+
+impl ClassSource for &str {
+    #[inline]
+    fn classes(&self) -> impl Iterator<Item = &str> + '_ {
+        self.split_whitespace()
+            .filter(|class| validate_class_name(class).is_ok())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl ClassSource for String {
+    #[inline]
+    fn classes(&self) -> impl Iterator<Item = &str> + '_ {
+        self.split_whitespace()
+            .filter(|class| validate_class_name(class).is_ok())
+    }
+}
+
+/**
+A custom group of mutually exclusive utility classes for a [`MergeTable`].
+A class belongs to the group if it's one of the group's `classes`, or if it's
+one of its `roots`, optionally followed by `-` and a value.
+
+If a custom group has the same name as one of the built-in Tailwind groups,
+its classes conflict with that group's classes.
+
+```rust
+use semester::MergeGroup;
+
+// `btn-sm`, `btn-md` and `btn-lg`
+const BUTTON_SIZE: MergeGroup = MergeGroup::new("button-size")
+    .with_classes(&["btn-sm", "btn-md", "btn-lg"]);
+
+// `elevation-1`, `elevation-2` and so on
+const ELEVATION: MergeGroup = MergeGroup::new("elevation").with_roots(&["elevation"]);
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MergeGroup<'a> {
+    name: &'a str,
+    classes: &'a [&'a str],
+    roots: &'a [&'a str],
+}
+
+impl<'a> MergeGroup<'a> {
+    /// Create an empty group with the given name.
+    #[inline]
+    #[must_use]
+    pub const fn new(name: &'a str) -> Self {
+        Self {
+            name,
+            classes: &[],
+            roots: &[],
+        }
+    }
+
+    /// Set the exact class names that belong to this group.
+    #[inline]
+    #[must_use]
+    pub const fn with_classes(self, classes: &'a [&'a str]) -> Self {
+        Self { classes, ..self }
+    }
+
+    /// Set the roots of this group. A class belongs to the group if it's a
+    /// root, or a root followed by `-` and a value.
+    #[inline]
+    #[must_use]
+    pub const fn with_roots(self, roots: &'a [&'a str]) -> Self {
+        Self { roots, ..self }
+    }
+
+    /// Get the name of this group.
+    #[inline]
+    #[must_use]
+    pub const fn name(&self) -> &'a str {
+        self.name
+    }
+}
+
+/**
+The table of utility groups that [`Classes::merge_with`] uses to decide which
+classes conflict. [`MergeTable::TAILWIND`] is the built-in table of
+[Tailwind](https://tailwindcss.com/) utilities that [`Classes::merge`] uses;
+it can be extended with custom groups, or replaced entirely with
+[`MergeTable::new`].
+
+Custom groups take precedence over the built-in ones. Classes that don't
+belong to any group only conflict with identical classes.
+
+```rust
+use semester::{classes, Classes as _, MergeGroup, MergeTable};
+
+const TABLE: MergeTable = MergeTable::TAILWIND
+    .with_groups(&[MergeGroup::new("button-size").with_classes(&["btn-sm", "btn-lg"])])
+    .with_prefix("tw-");
+
+let base = classes!("btn", "btn-sm", "tw-p-4");
+
+assert_eq!(
+    base.merge_with("btn-lg tw-p-8", &TABLE).to_string(),
+    "btn btn-lg tw-p-8",
+);
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MergeTable<'a> {
+    groups: &'a [MergeGroup<'a>],
+    includes: &'a [(&'a str, &'a [&'a str])],
+    prefix: Option<&'a str>,
+    tailwind: bool,
+}
+
+impl MergeTable<'static> {
+    /// The built-in table of Tailwind utility groups. This covers the most
+    /// common utilities, like spacing, sizing, layout, typography, colors
+    /// and borders. Utilities it doesn't recognize never conflict with
+    /// anything except identical classes.
+    pub const TAILWIND: Self = Self {
+        groups: &[],
+        includes: &[],
+        prefix: None,
+        tailwind: true,
+    };
+}
+
+impl<'a> MergeTable<'a> {
+    /// Create an empty table, without the built-in Tailwind groups.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            groups: &[],
+            includes: &[],
+            prefix: None,
+            tailwind: false,
+        }
+    }
+
+    /// Set the custom groups in this table. These are checked before the
+    /// built-in groups, if any.
+    #[inline]
+    #[must_use]
+    pub const fn with_groups(self, groups: &'a [MergeGroup<'a>]) -> Self {
+        Self { groups, ..self }
+    }
+
+    /// Set which groups include other groups, as `(parent, children)` pairs.
+    /// A class from the parent group overrides classes from any of its
+    /// children, but not the other way around: `p-4` overrides `px-2`, but
+    /// after `p-4`, `px-2` only overrides the horizontal padding, so both are
+    /// kept. These are in addition to the built-in relationships between the
+    /// Tailwind groups, if any.
+    #[inline]
+    #[must_use]
+    pub const fn with_includes(self, includes: &'a [(&'a str, &'a [&'a str])]) -> Self {
+        Self { includes, ..self }
+    }
+
+    /// Set the Tailwind prefix, like `tw-`. When there's a prefix, only
+    /// utilities with the prefix (after any variants) belong to the built-in
+    /// groups. Custom groups always match the whole utility.
+    #[inline]
+    #[must_use]
+    pub const fn with_prefix(self, prefix: &'a str) -> Self {
+        Self {
+            prefix: Some(prefix),
+            ..self
+        }
+    }
+
+    /// Find the group of a utility, without its variants or `!` modifier.
+    fn group(&self, utility: &str) -> Option<&'a str> {
+        if let Some(group) = self
+            .groups
+            .iter()
+            .find(|group| group.classes.contains(&utility))
+        {
+            return Some(group.name);
+        }
+
+        let root = self
+            .groups
+            .iter()
+            .flat_map(|group| group.roots.iter().map(move |root| (group.name, root)))
+            .filter(|(_, root)| match utility.strip_prefix(*root) {
+                Some(value) => value.is_empty() || value.starts_with('-'),
+                None => false,
+            })
+            .max_by_key(|(_, root)| root.len());
+
+        match root {
+            Some((group, _)) => Some(group),
+            None if self.tailwind => match self.prefix {
+                Some(prefix) => tailwind::group(utility.strip_prefix(prefix)?),
+                None => tailwind::group(utility),
+            },
+            None => None,
+        }
+    }
+
+    /// Check if a later class overrides an earlier one, because they're
+    /// identical, or because they have the same variants and `!` modifier
+    /// and the later class's group is the same as, or includes, the earlier
+    /// class's group.
+    fn overrides(&self, later: &str, earlier: &str) -> bool {
+        if later == earlier {
+            return true;
+        }
+
+        let (Some(later), Some(earlier)) = (Utility::parse(later), Utility::parse(earlier)) else {
+            return false;
+        };
+
+        if later.variants != earlier.variants || later.important != earlier.important {
+            return false;
+        }
+
+        let (Some(later), Some(earlier)) = (self.group(later.utility), self.group(earlier.utility))
+        else {
+            return false;
+        };
+
+        later == earlier
+            || self
+                .includes
+                .iter()
+                .any(|&(parent, children)| parent == later && children.contains(&earlier))
+            || (self.tailwind && tailwind::includes(later, earlier))
+    }
+}
+
+impl Default for MergeTable<'_> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A class split into its variants, `!` modifier and the utility itself.
+struct Utility<'a> {
+    variants: &'a str,
+    important: bool,
+    utility: &'a str,
+}
+
+impl<'a> Utility<'a> {
+    /// Split a class. The last `:` outside of square brackets ends the
+    /// variants, so `md:[&>*]:p-2` has the variants `md:[&>*]:`. Returns
+    /// `None` for arbitrary properties, like `[mask-type:alpha]`.
+    fn parse(class: &'a str) -> Option<Self> {
+        let mut depth = 0usize;
+        let mut split = 0;
+
+        for (i, c) in class.char_indices() {
+            match c {
+                '[' => depth += 1,
+                ']' => depth = depth.saturating_sub(1),
+                ':' if depth == 0 => split = i + 1,
+                _ => {}
+            }
+        }
+
+        let (variants, utility) = class.split_at(split);
+
+        let (important, utility) = match utility.strip_prefix('!') {
+            Some(utility) => (true, utility),
+            None => match utility.strip_suffix('!') {
+                Some(utility) => (true, utility),
+                None => (false, utility),
+            },
+        };
+
+        match utility.starts_with('[') {
+            true => None,
+            false => Some(Self {
+                variants,
+                important,
+                utility,
+            }),
+        }
+    }
+}
+
+/**
+A set of classes merged with overriding classes, created by
+[`Classes::merge`] or [`Classes::merge_with`]. This works like the
+JavaScript `tailwind-merge` library: a class is dropped if any later class
+overrides it, so the overriding classes win over conflicting classes from the
+set. Duplicate classes are only kept in their last position.
+
+Merging never allocates: the merged classes are computed lazily, whenever
+they're iterated or displayed.
+
+```rust
+use semester::{classes, Classes as _};
+
+let big = false;
+let base = classes!("card", "p-4", "text-sm", "text-lg": big);
+
+// The caller's padding and font size replace the defaults
+assert_eq!(
+    base.merge("p-8 text-xl shadow").to_string(),
+    "card p-8 text-xl shadow",
+);
+
+// `p-4` sets all the padding, so it overrides `px-2`, but not vice versa
+assert_eq!(classes!("px-2").merge("p-4").to_string(), "p-4");
+assert_eq!(classes!("p-4").merge("px-2").to_string(), "p-4 px-2");
+
+// Only utilities with the same variants conflict
+assert_eq!(
+    classes!("p-4", "md:p-6").merge("p-2").to_string(),
+    "md:p-6 p-2",
+);
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Merged<'t, C, S> {
+    classes: C,
+    overrides: S,
+    table: &'t MergeTable<'t>,
+}
+
+impl<'t, C: Classes, S: ClassSource> Merged<'t, C, S> {
+    #[inline]
+    #[must_use]
+    pub(crate) fn new(classes: C, overrides: S, table: &'t MergeTable<'t>) -> Self {
+        Self {
+            classes,
+            overrides,
+            table,
+        }
+    }
+
+    /// Get an iterator over all the classes, before any are dropped.
+    fn candidates(&self) -> impl Iterator<Item = &str> + '_ {
+        self.classes
+            .iter()
+            .map(|class| class as &str)
+            .chain(self.overrides.classes())
+    }
+
+    /// Get an iterator over the merged classes.
+    pub fn iter(&self) -> impl Iterator<Item = &str> + '_ {
+        self.candidates()
+            .enumerate()
+            .filter(move |&(idx, class)| {
+                !self
+                    .candidates()
+                    .skip(idx + 1)
+                    .any(|later| self.table.overrides(later, class))
+            })
+            .map(|(_, class)| class)
+    }
+
+    /// Get the number of merged classes.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    /// Check if there are no merged classes at all.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Render the merged classes by separating each one with a space. If
+    /// nothing was merged into the set, this will only allocate if
+    /// [`Classes::render`] would.
+    #[must_use]
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn render(&self) -> Cow<'_, str> {
+        match self.iter().eq(self.classes.iter()) {
+            true => self.classes.render(),
+            false => Cow::Owned(self.iter().fold(String::new(), |mut rendered, class| {
+                if !rendered.is_empty() {
+                    rendered.push(' ');
+                }
+                rendered.push_str(class);
+                rendered
+            })),
+        }
+    }
+}
+
+impl<C: Classes, S: ClassSource> Display for Merged<'_, C, S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.iter().enumerate().try_for_each(|(idx, class)| {
+            if idx > 0 {
+                f.write_str(" ")?;
+            }
+            f.write_str(class)
+        })
+    }
+}
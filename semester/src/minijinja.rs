@@ -0,0 +1,155 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+/*!
+[MiniJinja](https://docs.rs/minijinja/) integration.
+
+When the `minijinja` feature is enabled, every set created by [`classes`] or
+[`static_classes`], as well as [`ClassList`], can be converted into a
+`minijinja::Value`. The value renders as the space-separated classes, but it
+behaves like a list of classes in every other way: it has a `length`, it can
+be iterated, and it supports membership tests. No strings are copied for
+classes created by the macros.
+
+[`register`] adds a `classes(...)` function to an environment, which builds
+a [`ClassList`] from inside a template. Each argument can be a string of
+unconditional classes, or a mapping of class names to conditions; keyword
+arguments are treated as conditional classes as well. Every class name is
+validated with [`validate_class_name`][crate::validate_class_name].
+
+```rust
+use minijinja::{context, Environment, Value};
+use semester::classes;
+
+let mut env = Environment::new();
+semester::minijinja::register(&mut env);
+
+let active = true;
+let button: Value = classes!("button", "active": active).into();
+
+let template = "\
+    {{ button }}|{{ button | length }}|{{ 'active' in button }}|\
+    {{ classes('card', {'card-wide': wide}, selected=false) }}";
+
+let rendered = env
+    .render_str(template, context! { button, wide => true })
+    .unwrap();
+
+assert_eq!(rendered, "button active|2|True|card card-wide");
+```
+
+[`classes`]: crate::classes
+[`static_classes`]: crate::static_classes
+*/
+
+use std::{fmt, sync::Arc};
+
+use ::minijinja::{
+    value::{Enumerator, Kwargs, Object, ObjectRepr, Rest, ValueKind},
+    Environment, Error, ErrorKind, Value,
+};
+
+use crate::{ClassList, StaticClassSet};
+
+impl Object for ClassList {
+    fn repr(self: &Arc<Self>) -> ObjectRepr {
+        ObjectRepr::Seq
+    }
+
+    fn get_value(self: &Arc<Self>, key: &Value) -> Option<Value> {
+        let class = self.iter().nth(key.as_usize()?)?;
+        Some(Value::from(class))
+    }
+
+    fn enumerate(self: &Arc<Self>) -> Enumerator {
+        Enumerator::Seq(self.len())
+    }
+
+    fn is_true(self: &Arc<Self>) -> bool {
+        !self.is_empty()
+    }
+
+    fn render(self: &Arc<Self>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl From<ClassList> for Value {
+    #[inline]
+    fn from(list: ClassList) -> Self {
+        Value::from_object(list)
+    }
+}
+
+/// Insert a class from a template into a list, reporting invalid class
+/// names as template errors.
+fn insert(list: &mut ClassList, class: &str) -> Result<(), Error> {
+    list.insert(String::from(class)).map(|_| ()).map_err(|err| {
+        Error::new(
+            ErrorKind::InvalidOperation,
+            format!("class {class:?}: {err}"),
+        )
+    })
+}
+
+/// The `classes(...)` template function. See the [module docs][self] for
+/// details.
+pub fn classes(args: Rest<Value>, kwargs: Kwargs) -> Result<Value, Error> {
+    let mut list = ClassList::new();
+
+    for arg in args.iter() {
+        match arg.kind() {
+            ValueKind::String => {
+                for class in arg.as_str().unwrap_or_default().split_whitespace() {
+                    insert(&mut list, class)?;
+                }
+            }
+            ValueKind::Map => {
+                for name in arg.try_iter()? {
+                    if arg.get_item(&name)?.is_true() {
+                        insert(&mut list, &name.to_string())?;
+                    }
+                }
+            }
+            kind => {
+                return Err(Error::new(
+                    ErrorKind::InvalidOperation,
+                    format!("classes() expects strings or mappings, not {kind}"),
+                ))
+            }
+        }
+    }
+
+    for name in kwargs.args() {
+        if kwargs.get::<Value>(name)?.is_true() {
+            insert(&mut list, name)?;
+        }
+    }
+
+    Ok(Value::from(list))
+}
+
+/// Register the [`classes`] function with a MiniJinja environment.
+pub fn register(env: &mut Environment<'_>) {
+    env.add_function("classes", classes);
+}
+
+/// Implement `From<$type> for minijinja::Value`. Used by code generated by
+/// `semester-macro`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_minijinja {
+    ($type:ty) => {
+        impl ::core::convert::From<$type> for $crate::__minijinja::Value {
+            #[inline]
+            fn from(classes: $type) -> Self {
+                $crate::__minijinja::Value::from($crate::ClassList::from(classes))
+            }
+        }
+    };
+}
+
+__impl_minijinja!(StaticClassSet);
@@ -0,0 +1,92 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use core::fmt::{self, Display, Formatter};
+
+/// The reason a string was rejected as a class name by
+/// [`validate_class_name`]. These are the same rules that [`classes`] and
+/// [`static_classes`] enforce at compile time.
+///
+/// [`classes`]: crate::classes
+/// [`static_classes`]: crate::static_classes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ClassNameError {
+    /// The class name was empty.
+    Empty,
+
+    /// The class name contained whitespace.
+    Whitespace,
+
+    /// The class name contained one of the HTML unsafe characters `<>&'"`.
+    HtmlUnsafe,
+
+    /// The class name contained something other than ascii printable
+    /// characters. With the `unicode` feature, non-ASCII characters are
+    /// allowed, and this is never returned.
+    NotAsciiPrintable,
+
+    /// The class name contained a control character. This is only returned
+    /// with the `unicode` feature; otherwise, control characters are
+    /// [`NotAsciiPrintable`][ClassNameError::NotAsciiPrintable].
+    Control,
+}
+
+impl Display for ClassNameError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ClassNameError::Empty => "class name must not be empty",
+            ClassNameError::Whitespace => "class name must not include whitespace",
+            ClassNameError::HtmlUnsafe => {
+                "class name should not include HTML unsafe characters: <>&'\""
+            }
+            ClassNameError::NotAsciiPrintable => {
+                "class name must be only ascii printable characters"
+            }
+            ClassNameError::Control => "class name must not include control characters",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ClassNameError {}
+
+/// Check that a runtime string is a valid class name, using the same rules
+/// that the `semester` macros apply to class literals. By default, class names
+/// must be printable ASCII; with the `unicode` feature, they can include any
+/// character other than whitespace (including Unicode whitespace), control
+/// characters and `<>&'"`.
+///
+/// # Example
+///
+/// ```rust
+/// use semester::{validate_class_name, ClassNameError};
+///
+/// assert_eq!(validate_class_name("btn-primary"), Ok(()));
+/// assert_eq!(validate_class_name("a b"), Err(ClassNameError::Whitespace));
+/// assert_eq!(validate_class_name("<a>"), Err(ClassNameError::HtmlUnsafe));
+/// ```
+pub fn validate_class_name(class: &str) -> Result<(), ClassNameError> {
+    if class.is_empty() {
+        Err(ClassNameError::Empty)
+    } else if class.contains(char::is_whitespace) {
+        Err(ClassNameError::Whitespace)
+    } else if class
+        .bytes()
+        .any(|b| matches!(b, b'<' | b'>' | b'&' | b'\'' | b'"'))
+    {
+        Err(ClassNameError::HtmlUnsafe)
+    } else if cfg!(feature = "unicode") {
+        match class.contains(char::is_control) {
+            true => Err(ClassNameError::Control),
+            false => Ok(()),
+        }
+    } else if !class.bytes().all(|b| b.is_ascii_graphic()) {
+        Err(ClassNameError::NotAsciiPrintable)
+    } else {
+        Ok(())
+    }
+}
@@ -0,0 +1,350 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+/*!
+[`serde`](::serde) support for class sets.
+
+When the `serde` feature is enabled, every set created by [`classes`] or
+[`static_classes`] implements `Serialize` and `Deserialize`. By default a set
+is serialized as a single space-separated string; use the [`seq`] module with
+`#[serde(with = "...")]` to serialize it as a sequence of class names instead.
+
+Self-describing formats, like JSON, accept either form when deserializing.
+Formats that aren't self-describing, like `bincode` or `postcard`, can only
+read back the form that was written: a string by default, or a sequence when
+the [`seq`] module is used for both serializing and deserializing. Because the
+macros know the full set of classes that they could contain, deserializing
+maps each class name back onto its condition, and fails if it encounters a
+class that isn't part of the set or if an unconditional class is missing. The
+easiest way to deserialize into one of the anonymous macro types is to assign
+over an existing value:
+
+```rust
+use semester::{classes, Classes};
+use serde::{de::DeserializeOwned, Serialize};
+
+fn button(primary: bool, active: bool) -> impl Classes + Serialize + DeserializeOwned {
+    classes!("btn", "btn-primary": primary, "active": active)
+}
+
+let mut classes = button(false, false);
+classes = serde_json::from_str(r#""btn active""#).unwrap();
+assert_eq!(classes.render(), "btn active");
+
+classes = serde_json::from_str(r#"["btn", "btn-primary"]"#).unwrap();
+assert_eq!(classes.render(), "btn btn-primary");
+
+assert_eq!(serde_json::to_string(&classes).unwrap(), r#""btn btn-primary""#);
+
+// Unknown classes are rejected
+assert!(serde_json::from_str(r#""btn shadow""#).map(|c| classes = c).is_err());
+
+// So are sets missing an unconditional class
+assert!(serde_json::from_str(r#""active""#).map(|c| classes = c).is_err());
+```
+
+[`ClassList`][crate::ClassList] also implements `Serialize` and
+`Deserialize` when the `alloc` feature is enabled; it accepts any valid class
+names.
+
+[`classes`]: crate::classes
+[`static_classes`]: crate::static_classes
+*/
+
+use core::fmt::{self, Formatter};
+
+use ::serde::{
+    de::{self, DeserializeSeed, SeqAccess, Visitor},
+    Deserializer, Serializer,
+};
+
+use crate::{StaticClassSet, StaticClasses};
+
+/**
+Serialize a class set as a single space-separated string. This is the default
+format, but the module is provided for symmetry with [`seq`], for use with
+`#[serde(with = "semester::serde::joined")]`.
+*/
+pub mod joined {
+    use ::serde::{Deserialize, Deserializer, Serializer};
+
+    use crate::Classes;
+
+    /// Serialize a class set as a space-separated string. Doesn't allocate
+    /// if [`try_as_str`][Classes::try_as_str] succeeds.
+    pub fn serialize<C: Classes, S: Serializer>(
+        classes: &C,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match classes.try_as_str() {
+            Some(rendered) => serializer.serialize_str(rendered),
+            None => serializer.collect_str(classes),
+        }
+    }
+
+    /// Deserialize a class set. In self-describing formats this accepts
+    /// either a space-separated string or a sequence of class names; in other
+    /// formats, it expects a string.
+    #[inline]
+    pub fn deserialize<'de, C: Deserialize<'de>, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<C, D::Error> {
+        C::deserialize(deserializer)
+    }
+}
+
+/**
+Serialize a class set as a sequence of class names, for use with
+`#[serde(with = "semester::serde::seq")]`.
+
+```rust
+use semester::{classes, Classes};
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct Fragment<C: Classes> {
+    #[serde(serialize_with = "semester::serde::seq::serialize")]
+    classes: C,
+}
+
+let fragment = Fragment {
+    classes: classes!("card", "active": true),
+};
+
+assert_eq!(
+    serde_json::to_string(&fragment).unwrap(),
+    r#"{"classes":["card","active"]}"#,
+);
+```
+*/
+pub mod seq {
+    use ::serde::{ser::SerializeSeq as _, Deserialize, Deserializer, Serializer};
+
+    use crate::Classes;
+
+    /// Serialize a class set as a sequence of class names.
+    pub fn serialize<C: Classes, S: Serializer>(
+        classes: &C,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        // The length is needed by formats that aren't self-describing, and
+        // `collect_seq` only passes it along for exact size iterators.
+        let mut seq = serializer.serialize_seq(Some(classes.len()))?;
+        classes
+            .iter()
+            .try_for_each(|class| seq.serialize_element(class))?;
+        seq.end()
+    }
+
+    /// Deserialize a class set. In self-describing formats this accepts
+    /// either a space-separated string or a sequence of class names; in other
+    /// formats, it expects a sequence.
+    #[inline]
+    pub fn deserialize<'de, C: Deserialize<'de>, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<C, D::Error> {
+        C::deserialize(super::SeqHint(deserializer))
+    }
+}
+
+/// A deserializer that asks formats that aren't self-describing for a
+/// sequence, instead of the string that class sets use by default.
+struct SeqHint<D>(D);
+
+impl<'de, D: Deserializer<'de>> Deserializer<'de> for SeqHint<D> {
+    type Error = D::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, D::Error> {
+        match self.0.is_human_readable() {
+            true => self.0.deserialize_any(visitor),
+            false => self.0.deserialize_seq(visitor),
+        }
+    }
+
+    #[inline]
+    fn is_human_readable(&self) -> bool {
+        self.0.is_human_readable()
+    }
+
+    ::serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+/// Deserialize a class set with `visitor`. Self-describing formats accept
+/// either a string or a sequence; other formats only have the string that
+/// `Serialize` writes by default, unless [`SeqHint`] asks for a sequence.
+fn deserialize_classes<'de, D: Deserializer<'de>, V: Visitor<'de>>(
+    deserializer: D,
+    visitor: V,
+) -> Result<V::Value, D::Error> {
+    match deserializer.is_human_readable() {
+        true => deserializer.deserialize_any(visitor),
+        false => deserializer.deserialize_str(visitor),
+    }
+}
+
+impl ::serde::Serialize for StaticClassSet {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// Tracks which of a known set of classes have been seen during
+/// deserialization.
+struct FlagSet<const N: usize> {
+    classes: &'static [&'static str; N],
+    flags: [bool; N],
+}
+
+impl<const N: usize> FlagSet<N> {
+    fn insert<E: de::Error>(&mut self, class: &str) -> Result<(), E> {
+        let idx = self
+            .classes
+            .iter()
+            .position(|&known| known == class)
+            .ok_or_else(|| E::custom(format_args!("unknown class `{class}`")))?;
+
+        match core::mem::replace(&mut self.flags[idx], true) {
+            false => Ok(()),
+            true => Err(E::custom(format_args!("duplicate class `{class}`"))),
+        }
+    }
+
+    fn finish<E: de::Error>(self, required: &[bool; N]) -> Result<[bool; N], E> {
+        match self
+            .flags
+            .iter()
+            .zip(required)
+            .position(|(&flag, &required)| required && !flag)
+        {
+            None => Ok(self.flags),
+            Some(idx) => Err(E::custom(format_args!(
+                "missing required class `{}`",
+                self.classes[idx]
+            ))),
+        }
+    }
+}
+
+impl<'de, const N: usize> Visitor<'de> for FlagSet<N> {
+    type Value = Self;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str("a space-separated string or a sequence of class names")
+    }
+
+    fn visit_str<E: de::Error>(mut self, classes: &str) -> Result<Self, E> {
+        classes
+            .split_whitespace()
+            .try_for_each(|class| self.insert(class))?;
+
+        Ok(self)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(mut self, mut seq: A) -> Result<Self, A::Error> {
+        while let Some(()) = seq.next_element_seed(ClassSeed(&mut self))? {}
+        Ok(self)
+    }
+}
+
+/// Deserialize a single class name from a sequence and record it in a
+/// `FlagSet`, without allocating.
+struct ClassSeed<'a, const N: usize>(&'a mut FlagSet<N>);
+
+impl<'de, const N: usize> DeserializeSeed<'de> for ClassSeed<'_, N> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl<'de, const N: usize> Visitor<'de> for ClassSeed<'_, N> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str("a class name")
+    }
+
+    fn visit_str<E: de::Error>(self, class: &str) -> Result<(), E> {
+        self.0.insert(class)
+    }
+}
+
+/// Deserialize a set of classes drawn from `classes`, returning a flag for
+/// each one indicating if it was present. Used by code generated by
+/// `semester-macro`.
+#[doc(hidden)]
+pub fn deserialize_flags<'de, D: Deserializer<'de>, const N: usize>(
+    deserializer: D,
+    classes: &'static [&'static str; N],
+    required: &[bool; N],
+) -> Result<[bool; N], D::Error> {
+    deserialize_classes(
+        deserializer,
+        FlagSet {
+            classes,
+            flags: [false; N],
+        },
+    )?
+    .finish(required)
+}
+
+#[cfg(feature = "alloc")]
+mod list {
+    use core::fmt::{self, Formatter};
+
+    use alloc::string::String;
+
+    use ::serde::{
+        de::{self, SeqAccess, Visitor},
+        Deserialize, Deserializer, Serialize, Serializer,
+    };
+
+    use crate::ClassList;
+
+    impl Serialize for ClassList {
+        #[inline]
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_str(self)
+        }
+    }
+
+    struct ClassListVisitor;
+
+    impl<'de> Visitor<'de> for ClassListVisitor {
+        type Value = ClassList;
+
+        fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+            formatter.write_str("a space-separated string or a sequence of class names")
+        }
+
+        fn visit_str<E: de::Error>(self, classes: &str) -> Result<ClassList, E> {
+            ClassList::parse(classes).map_err(E::custom)
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<ClassList, A::Error> {
+            let mut list = ClassList::new();
+
+            while let Some(class) = seq.next_element::<String>()? {
+                list.insert(class).map_err(de::Error::custom)?;
+            }
+
+            Ok(list)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for ClassList {
+        #[inline]
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            super::deserialize_classes(deserializer, ClassListVisitor)
+        }
+    }
+}
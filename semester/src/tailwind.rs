@@ -0,0 +1,401 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! The built-in table of [Tailwind](https://tailwindcss.com/) utility groups.
+//! This file is shared by both crates: `semester` uses it for
+//! `MergeTable::TAILWIND`, and `semester-macro` keeps an identical copy in
+//! `src/tailwind/groups.rs` for the `tailwind-conflicts` lint, so that the
+//! two always classify utilities the same way. Edit both copies together.
+
+/// Groups whose classes are a fixed set of names.
+const EXACT_GROUPS: &[(&str, &[&str])] = &[
+    (
+        "display",
+        &[
+            "block",
+            "inline-block",
+            "inline",
+            "flex",
+            "inline-flex",
+            "table",
+            "inline-table",
+            "table-caption",
+            "table-cell",
+            "table-column",
+            "table-column-group",
+            "table-footer-group",
+            "table-header-group",
+            "table-row-group",
+            "table-row",
+            "flow-root",
+            "grid",
+            "inline-grid",
+            "contents",
+            "list-item",
+            "hidden",
+        ],
+    ),
+    (
+        "position",
+        &["static", "fixed", "absolute", "relative", "sticky"],
+    ),
+    ("visibility", &["visible", "invisible", "collapse"]),
+    ("box-sizing", &["box-border", "box-content"]),
+    ("isolation", &["isolate", "isolation-auto"]),
+    (
+        "text-transform",
+        &["uppercase", "lowercase", "capitalize", "normal-case"],
+    ),
+    ("font-style", &["italic", "not-italic"]),
+    (
+        "text-decoration-line",
+        &["underline", "overline", "line-through", "no-underline"],
+    ),
+    (
+        "flex-direction",
+        &[
+            "flex-row",
+            "flex-row-reverse",
+            "flex-col",
+            "flex-col-reverse",
+        ],
+    ),
+    (
+        "flex-wrap",
+        &["flex-wrap", "flex-wrap-reverse", "flex-nowrap"],
+    ),
+    ("border-collapse", &["border-collapse", "border-separate"]),
+];
+
+/// Groups whose classes are `root` or `root-value`, for any value. When
+/// several roots match, the longest one wins, so `px-2` is `padding-x`, not
+/// `padding`.
+const ROOT_GROUPS: &[(&str, &str)] = &[
+    ("p", "padding"),
+    ("px", "padding-x"),
+    ("py", "padding-y"),
+    ("pt", "padding-t"),
+    ("pr", "padding-r"),
+    ("pb", "padding-b"),
+    ("pl", "padding-l"),
+    ("ps", "padding-s"),
+    ("pe", "padding-e"),
+    ("m", "margin"),
+    ("mx", "margin-x"),
+    ("my", "margin-y"),
+    ("mt", "margin-t"),
+    ("mr", "margin-r"),
+    ("mb", "margin-b"),
+    ("ml", "margin-l"),
+    ("ms", "margin-s"),
+    ("me", "margin-e"),
+    ("w", "width"),
+    ("min-w", "min-width"),
+    ("max-w", "max-width"),
+    ("h", "height"),
+    ("min-h", "min-height"),
+    ("max-h", "max-height"),
+    ("size", "size"),
+    ("gap", "gap"),
+    ("gap-x", "gap-x"),
+    ("gap-y", "gap-y"),
+    ("inset", "inset"),
+    ("inset-x", "inset-x"),
+    ("inset-y", "inset-y"),
+    ("top", "top"),
+    ("right", "right"),
+    ("bottom", "bottom"),
+    ("left", "left"),
+    ("start", "start"),
+    ("end", "end"),
+    ("z", "z-index"),
+    ("order", "order"),
+    ("opacity", "opacity"),
+    ("basis", "flex-basis"),
+    ("grow", "flex-grow"),
+    ("shrink", "flex-shrink"),
+    ("flex", "flex"),
+    ("grid-cols", "grid-template-columns"),
+    ("grid-rows", "grid-template-rows"),
+    ("grid-flow", "grid-auto-flow"),
+    ("col-span", "grid-column-span"),
+    ("row-span", "grid-row-span"),
+    ("justify", "justify-content"),
+    ("justify-items", "justify-items"),
+    ("justify-self", "justify-self"),
+    ("items", "align-items"),
+    ("self", "align-self"),
+    ("content", "align-content"),
+    ("place-content", "place-content"),
+    ("place-items", "place-items"),
+    ("place-self", "place-self"),
+    ("leading", "line-height"),
+    ("tracking", "letter-spacing"),
+    ("whitespace", "white-space"),
+    ("align", "vertical-align"),
+    ("overflow", "overflow"),
+    ("overflow-x", "overflow-x"),
+    ("overflow-y", "overflow-y"),
+    ("cursor", "cursor"),
+    ("select", "user-select"),
+    ("pointer-events", "pointer-events"),
+    ("aspect", "aspect-ratio"),
+    ("columns", "columns"),
+    ("transition", "transition-property"),
+    ("duration", "transition-duration"),
+    ("ease", "transition-timing-function"),
+    ("delay", "transition-delay"),
+    ("translate-x", "translate-x"),
+    ("translate-y", "translate-y"),
+    ("rotate", "rotate"),
+    ("scale", "scale"),
+    ("scale-x", "scale-x"),
+    ("scale-y", "scale-y"),
+    ("skew-x", "skew-x"),
+    ("skew-y", "skew-y"),
+    ("rounded", "rounded"),
+    ("rounded-s", "rounded-s"),
+    ("rounded-e", "rounded-e"),
+    ("rounded-t", "rounded-t"),
+    ("rounded-r", "rounded-r"),
+    ("rounded-b", "rounded-b"),
+    ("rounded-l", "rounded-l"),
+    ("rounded-tl", "rounded-tl"),
+    ("rounded-tr", "rounded-tr"),
+    ("rounded-br", "rounded-br"),
+    ("rounded-bl", "rounded-bl"),
+    ("border-spacing", "border-spacing"),
+    ("bg-clip", "background-clip"),
+    ("bg-origin", "background-origin"),
+    ("bg-blend", "background-blend-mode"),
+    ("ring-offset", "ring-offset"),
+    // These depend on the value, and are resolved by `refine`
+    ("text", "text"),
+    ("font", "font"),
+    ("bg", "bg"),
+    ("shadow", "shadow"),
+    ("ring", "ring"),
+    ("border", "border"),
+    ("border-x", "border-x"),
+    ("border-y", "border-y"),
+    ("border-t", "border-t"),
+    ("border-r", "border-r"),
+    ("border-b", "border-b"),
+    ("border-l", "border-l"),
+    ("border-s", "border-s"),
+    ("border-e", "border-e"),
+    ("object", "object"),
+];
+
+/// Groups that override other groups: for instance, `p-4` sets all the
+/// padding, so it conflicts with `px-2`.
+const CONFLICTING_GROUPS: &[(&str, &[&str])] = &[
+    (
+        "padding",
+        &[
+            "padding-x",
+            "padding-y",
+            "padding-t",
+            "padding-r",
+            "padding-b",
+            "padding-l",
+            "padding-s",
+            "padding-e",
+        ],
+    ),
+    ("padding-x", &["padding-r", "padding-l"]),
+    ("padding-y", &["padding-t", "padding-b"]),
+    (
+        "margin",
+        &[
+            "margin-x", "margin-y", "margin-t", "margin-r", "margin-b", "margin-l", "margin-s",
+            "margin-e",
+        ],
+    ),
+    ("margin-x", &["margin-r", "margin-l"]),
+    ("margin-y", &["margin-t", "margin-b"]),
+    ("size", &["width", "height"]),
+    ("gap", &["gap-x", "gap-y"]),
+    (
+        "inset",
+        &[
+            "inset-x", "inset-y", "top", "right", "bottom", "left", "start", "end",
+        ],
+    ),
+    ("inset-x", &["right", "left"]),
+    ("inset-y", &["top", "bottom"]),
+    ("overflow", &["overflow-x", "overflow-y"]),
+    ("scale", &["scale-x", "scale-y"]),
+    (
+        "rounded",
+        &[
+            "rounded-s",
+            "rounded-e",
+            "rounded-t",
+            "rounded-r",
+            "rounded-b",
+            "rounded-l",
+            "rounded-tl",
+            "rounded-tr",
+            "rounded-br",
+            "rounded-bl",
+        ],
+    ),
+    ("rounded-t", &["rounded-tl", "rounded-tr"]),
+    ("rounded-r", &["rounded-tr", "rounded-br"]),
+    ("rounded-b", &["rounded-br", "rounded-bl"]),
+    ("rounded-l", &["rounded-tl", "rounded-bl"]),
+    (
+        "border-width",
+        &[
+            "border-width-x",
+            "border-width-y",
+            "border-width-t",
+            "border-width-r",
+            "border-width-b",
+            "border-width-l",
+            "border-width-s",
+            "border-width-e",
+        ],
+    ),
+    ("border-width-x", &["border-width-r", "border-width-l"]),
+    ("border-width-y", &["border-width-t", "border-width-b"]),
+    (
+        "border-color",
+        &[
+            "border-color-x",
+            "border-color-y",
+            "border-color-t",
+            "border-color-r",
+            "border-color-b",
+            "border-color-l",
+            "border-color-s",
+            "border-color-e",
+        ],
+    ),
+    ("border-color-x", &["border-color-r", "border-color-l"]),
+    ("border-color-y", &["border-color-t", "border-color-b"]),
+];
+
+/// Check whether a value is a length, like `2`, `px` or `[3rem]`.
+fn is_length(value: &str) -> bool {
+    let value = value
+        .strip_prefix('[')
+        .and_then(|value| value.strip_suffix(']'))
+        .unwrap_or(value);
+
+    value.is_empty() || value == "px" || value.starts_with(|c: char| c.is_ascii_digit())
+}
+
+/// Resolve the groups whose meaning depends on the value, like `text-lg`
+/// (font size) and `text-red-500` (text color).
+fn refine(group: &'static str, value: &str) -> &'static str {
+    match group {
+        "text" => match value {
+            "xs" | "sm" | "base" | "lg" | "xl" | "2xl" | "3xl" | "4xl" | "5xl" | "6xl" | "7xl"
+            | "8xl" | "9xl" => "font-size",
+            "left" | "center" | "right" | "justify" | "start" | "end" => "text-align",
+            "ellipsis" | "clip" => "text-overflow",
+            "wrap" | "nowrap" | "balance" | "pretty" => "text-wrap",
+            value if value.starts_with('[') && is_length(value) => "font-size",
+            _ => "text-color",
+        },
+        "font" => match value {
+            "thin" | "extralight" | "light" | "normal" | "medium" | "semibold" | "bold"
+            | "extrabold" | "black" => "font-weight",
+            _ => "font-family",
+        },
+        "bg" => match value {
+            "fixed" | "local" | "scroll" => "background-attachment",
+            "auto" | "cover" | "contain" => "background-size",
+            "bottom" | "center" | "left" | "left-bottom" | "left-top" | "right"
+            | "right-bottom" | "right-top" | "top" => "background-position",
+            "repeat" | "no-repeat" | "repeat-x" | "repeat-y" | "repeat-round" | "repeat-space" => {
+                "background-repeat"
+            }
+            value if value == "none" || value.starts_with("gradient-to-") => "background-image",
+            _ => "background-color",
+        },
+        "shadow" => match value {
+            "" | "sm" | "md" | "lg" | "xl" | "2xl" | "inner" | "none" => "box-shadow",
+            _ => "box-shadow-color",
+        },
+        "ring" => match value {
+            "inset" => "ring-inset",
+            value if is_length(value) => "ring-width",
+            _ => "ring-color",
+        },
+        "ring-offset" => match is_length(value) {
+            true => "ring-offset-width",
+            false => "ring-offset-color",
+        },
+        "object" => match value {
+            "contain" | "cover" | "fill" | "none" | "scale-down" => "object-fit",
+            _ => "object-position",
+        },
+        "border" => match value {
+            "solid" | "dashed" | "dotted" | "double" | "hidden" | "none" => "border-style",
+            value if is_length(value) => "border-width",
+            _ => "border-color",
+        },
+        side if side.starts_with("border-") => {
+            let width = is_length(value);
+            match (side, width) {
+                ("border-x", true) => "border-width-x",
+                ("border-y", true) => "border-width-y",
+                ("border-t", true) => "border-width-t",
+                ("border-r", true) => "border-width-r",
+                ("border-b", true) => "border-width-b",
+                ("border-l", true) => "border-width-l",
+                ("border-s", true) => "border-width-s",
+                ("border-e", true) => "border-width-e",
+                ("border-x", false) => "border-color-x",
+                ("border-y", false) => "border-color-y",
+                ("border-t", false) => "border-color-t",
+                ("border-r", false) => "border-color-r",
+                ("border-b", false) => "border-color-b",
+                ("border-l", false) => "border-color-l",
+                ("border-s", false) => "border-color-s",
+                ("border-e", false) => "border-color-e",
+                _ => group,
+            }
+        }
+        _ => group,
+    }
+}
+
+/// Find the group of a Tailwind utility, without its variants, `!` modifier
+/// or prefix. Returns `None` for utilities that aren't recognized.
+pub(crate) fn group(utility: &str) -> Option<&'static str> {
+    if let Some(&(group, _)) = EXACT_GROUPS
+        .iter()
+        .find(|(_, classes)| classes.contains(&utility))
+    {
+        return Some(group);
+    }
+
+    // Negative values, like `-mt-2`
+    let utility = utility.strip_prefix('-').unwrap_or(utility);
+
+    let (root, group) = ROOT_GROUPS
+        .iter()
+        .filter(|(root, _)| match utility.strip_prefix(root) {
+            Some(value) => value.is_empty() || value.starts_with('-'),
+            None => false,
+        })
+        .max_by_key(|(root, _)| root.len())?;
+
+    let value = utility[root.len()..].trim_start_matches('-');
+    Some(refine(group, value))
+}
+
+/// Check if `parent` is a group that includes all of `child`, like
+/// `padding` and `padding-x`.
+pub(crate) fn includes(parent: &str, child: &str) -> bool {
+    CONFLICTING_GROUPS
+        .iter()
+        .any(|&(group, children)| group == parent && children.contains(&child))
+}
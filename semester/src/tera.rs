@@ -0,0 +1,181 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+/*!
+[Tera](https://docs.rs/tera/) integration.
+
+When the `tera` feature is enabled, every set created by [`classes`] or
+[`static_classes`], as well as [`ClassList`], can be converted into a
+`tera::Value`, which renders as the space-separated classes.
+
+Tera values are plain JSON values, so a set of classes has to be a string,
+and Tera's own `length` filter and `in` operator treat it like one: they
+count characters and search for substrings. [`register`] adds helpers to a
+`Tera` instance that treat the string as a set of classes instead:
+
+- a `class_count` filter, which counts the classes;
+- a `has_class(name)` test, which checks if a class is present;
+- a `classes(...)` function, which builds a [`ClassList`] from keyword
+  arguments mapping class names to conditions. Every class name is
+  validated with [`validate_class_name`][crate::validate_class_name].
+
+Tera identifiers can't contain `-`, so class names like `card-wide` can't be
+keyword arguments. Instead, pass an object mapping class names to conditions
+as the `map` argument, like `classes(map=states)`. Tera doesn't have object
+literals, so the object has to come from the context. Keyword arguments can
+be combined with `map`, and take precedence over it. `map` itself is always
+this argument, so it's an error if it isn't an object; a class named `map`
+has to be passed in the object.
+
+```rust
+use semester::classes;
+use tera::{Context, Tera, Value};
+
+let mut tera = Tera::default();
+semester::tera::register(&mut tera);
+
+let active = true;
+let button: Value = classes!("button", "active": active).into();
+
+let mut context = Context::new();
+context.insert("button", &button);
+context.insert("wide", &true);
+
+let template = "\
+    {{ button }}|{{ button | class_count }}|{{ button is has_class('active') }}|\
+    {{ classes(card=true, wide=wide, selected=false) }}";
+
+let rendered = tera.render_str(template, &context).unwrap();
+
+assert_eq!(rendered, "button active|2|true|card wide");
+
+context.insert("states", &serde_json::json!({ "card-wide": true, "card-dark": false }));
+let rendered = tera.render_str("{{ classes(map=states, card=true) }}", &context);
+
+assert_eq!(rendered.unwrap(), "card card-wide");
+```
+
+[`classes`]: crate::classes
+[`static_classes`]: crate::static_classes
+*/
+
+use std::collections::{BTreeMap, HashMap};
+
+use ::tera::{Error, Result, Tera, Value};
+
+use crate::{ClassList, Classes, StaticClassSet};
+
+impl From<ClassList> for Value {
+    #[inline]
+    fn from(list: ClassList) -> Self {
+        Value::String(list.to_string())
+    }
+}
+
+/// Convert a class set to a `tera::Value`.
+#[must_use]
+pub fn to_value(classes: &impl Classes) -> Value {
+    Value::String(classes.render().into_owned())
+}
+
+/// Check if a Tera value is truthy, using the same rules as Tera.
+fn is_truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(value) => *value,
+        Value::Number(number) => number.as_f64().is_some_and(|n| n != 0.0 && !n.is_nan()),
+        Value::String(value) => !value.is_empty(),
+        Value::Array(values) => !values.is_empty(),
+        Value::Object(values) => !values.is_empty(),
+    }
+}
+
+/// Get the classes in a Tera string value.
+fn split<'a>(value: &'a Value, context: &str) -> Result<impl Iterator<Item = &'a str>> {
+    match value {
+        Value::String(classes) => Ok(classes.split_whitespace()),
+        _ => Err(Error::msg(format!(
+            "{context} expects a string of classes, not {value}"
+        ))),
+    }
+}
+
+/// The `class_count` filter. See the [module docs][self] for details.
+pub fn class_count(value: &Value, _args: &HashMap<String, Value>) -> Result<Value> {
+    Ok(Value::from(split(value, "class_count")?.count()))
+}
+
+/// The `has_class` test. See the [module docs][self] for details.
+pub fn has_class(value: Option<&Value>, args: &[Value]) -> Result<bool> {
+    let class = match args {
+        [Value::String(class)] => class,
+        _ => return Err(Error::msg("has_class expects a single class name")),
+    };
+
+    match value {
+        None => Ok(false),
+        Some(value) => Ok(split(value, "has_class")?.any(|existing| existing == class)),
+    }
+}
+
+/// The `classes(...)` template function. See the [module docs][self] for
+/// details.
+pub fn classes(args: &HashMap<String, Value>) -> Result<Value> {
+    let mut list = ClassList::new();
+
+    let map = match args.get("map") {
+        None => None,
+        Some(Value::Object(map)) => Some(map),
+        Some(value) => {
+            return Err(Error::msg(format!(
+                "classes expects `map` to be an object of class names and conditions, \
+                not {value}"
+            )))
+        }
+    };
+
+    // Tera passes arguments as a `HashMap`, so sort them to make the output
+    // deterministic. Keyword arguments are collected last, so that they
+    // override the map.
+    map.into_iter()
+        .flatten()
+        .chain(args.iter().filter(|&(name, _)| name != "map"))
+        .collect::<BTreeMap<_, _>>()
+        .into_iter()
+        .filter(|&(_, condition)| is_truthy(condition))
+        .try_for_each(|(class, _)| {
+            list.insert(class.clone())
+                .map(|_| ())
+                .map_err(|err| Error::msg(format!("class {class:?}: {err}")))
+        })?;
+
+    Ok(Value::from(list))
+}
+
+/// Register the [`class_count`] filter, the [`has_class`] test, and the
+/// [`classes`] function with a Tera instance.
+pub fn register(tera: &mut Tera) {
+    tera.register_filter("class_count", class_count);
+    tera.register_tester("has_class", has_class);
+    tera.register_function("classes", classes);
+}
+
+/// Implement `From<$type> for tera::Value`. Used by code generated by
+/// `semester-macro`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_tera {
+    ($type:ty) => {
+        impl ::core::convert::From<$type> for $crate::__tera::Value {
+            #[inline]
+            fn from(classes: $type) -> Self {
+                $crate::tera::to_value(&classes)
+            }
+        }
+    };
+}
+
+__impl_tera!(StaticClassSet);
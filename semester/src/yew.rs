@@ -0,0 +1,201 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+/*!
+[Yew](https://docs.rs/yew/) integration.
+
+When the `yew` feature is enabled, every set created by [`classes`] or
+[`static_classes`] can be used directly as a `class=` attribute or a
+`classes=` prop, without going through [`render`][Classes::render]:
+
+- `Into<yew::Classes>`, which is what `class={...}` on an element uses. Each
+  class is inserted as an `AttrValue::Static`, so no strings are allocated.
+- `IntoPropValue<yew::Classes>`, `IntoPropValue<AttrValue>` and
+  `IntoPropValue<Option<AttrValue>>`, for component props. If
+  [`try_as_str`][Classes::try_as_str] succeeds, the `AttrValue` is a
+  `&'static str` and nothing is allocated.
+- `ToHtml`, which renders the classes as text.
+
+```rust
+use semester::{classes, Classes as _};
+use yew::{html, AttrValue, html::IntoPropValue};
+
+let active = true;
+let classes = classes!("button", "active": active);
+
+let _ = html! { <button class={classes}>{"Click"}</button> };
+
+let classes = classes!("button", "active": !active);
+let value: AttrValue = classes.into_prop_value();
+assert!(matches!(value, AttrValue::Static("button")));
+```
+
+[`ClassList`][crate::ClassList] implements the same conversions.
+
+[`classes`]: crate::classes
+[`static_classes`]: crate::static_classes
+*/
+
+use std::rc::Rc;
+
+use ::yew::{
+    html::{IntoPropValue, ToHtml},
+    virtual_dom::VText,
+    AttrValue, Html,
+};
+
+use crate::{ClassList, Classes, StaticClassSet};
+
+/// Convert a class set to a [`yew::Classes`](::yew::html::Classes). Every
+/// class is inserted as a `&'static str`, so no strings are allocated.
+#[must_use]
+pub fn to_classes(classes: &impl Classes) -> ::yew::html::Classes {
+    let mut yew_classes = ::yew::html::Classes::with_capacity(classes.len());
+    classes.iter().for_each(|class| yew_classes.push(class));
+    yew_classes
+}
+
+/// Convert a class set to a [`yew::Classes`](::yew::html::Classes), like
+/// [`to_classes`], without checking the classes again. Used by code
+/// generated by `semester-macro`.
+///
+/// # Safety
+///
+/// Every class in `classes` must be nonempty and must not contain
+/// whitespace. This is true of every class set created by the macros, but
+/// not necessarily of other `Classes` implementations.
+#[doc(hidden)]
+#[must_use]
+pub unsafe fn to_classes_unchecked(classes: &impl Classes) -> ::yew::html::Classes {
+    let mut yew_classes = ::yew::html::Classes::with_capacity(classes.len());
+
+    classes.iter().for_each(|class| {
+        // Safety: guaranteed by the caller.
+        unsafe { yew_classes.unchecked_push(AttrValue::Static(class)) }
+    });
+
+    yew_classes
+}
+
+/// Convert a class set to an [`AttrValue`]. If
+/// [`try_as_str`][Classes::try_as_str] succeeds, this will be an
+/// `AttrValue::Static`, and won't allocate.
+#[must_use]
+pub fn to_attr_value(classes: &impl Classes) -> AttrValue {
+    match classes.try_as_str() {
+        Some(rendered) => AttrValue::Static(rendered),
+        None => AttrValue::Rc(Rc::from(classes.to_string())),
+    }
+}
+
+/// Convert a class set to an [`Html`] text node.
+pub fn to_html(classes: &impl Classes) -> Html {
+    Html::VText(VText::new(to_attr_value(classes)))
+}
+
+/// Implement all the Yew conversion traits for a `Classes` type. Used by
+/// code generated by `semester-macro`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_yew {
+    ($type:ty) => {
+        impl ::core::convert::From<$type> for $crate::__yew::html::Classes {
+            #[inline]
+            fn from(classes: $type) -> Self {
+                // Safety: this is only used for class sets created by the
+                // macros, whose classes are validated at compile time.
+                unsafe { $crate::yew::to_classes_unchecked(&classes) }
+            }
+        }
+
+        impl $crate::__yew::html::IntoPropValue<$crate::__yew::html::Classes> for $type {
+            #[inline]
+            fn into_prop_value(self) -> $crate::__yew::html::Classes {
+                // Safety: as above.
+                unsafe { $crate::yew::to_classes_unchecked(&self) }
+            }
+        }
+
+        impl $crate::__yew::html::IntoPropValue<$crate::__yew::AttrValue> for $type {
+            #[inline]
+            fn into_prop_value(self) -> $crate::__yew::AttrValue {
+                $crate::yew::to_attr_value(&self)
+            }
+        }
+
+        impl $crate::__yew::html::IntoPropValue<::core::option::Option<$crate::__yew::AttrValue>>
+            for $type
+        {
+            #[inline]
+            fn into_prop_value(self) -> ::core::option::Option<$crate::__yew::AttrValue> {
+                match $crate::Classes::len(&self) {
+                    0 => ::core::option::Option::None,
+                    _ => ::core::option::Option::Some($crate::yew::to_attr_value(&self)),
+                }
+            }
+        }
+
+        impl $crate::__yew::html::ToHtml for $type {
+            #[inline]
+            fn to_html(&self) -> $crate::__yew::Html {
+                $crate::yew::to_html(self)
+            }
+        }
+    };
+}
+
+__impl_yew!(StaticClassSet);
+
+impl From<ClassList> for ::yew::html::Classes {
+    fn from(list: ClassList) -> Self {
+        Self::from(&list)
+    }
+}
+
+impl From<&ClassList> for ::yew::html::Classes {
+    fn from(list: &ClassList) -> Self {
+        let mut yew_classes = Self::with_capacity(list.len());
+
+        list.iter().for_each(|class| {
+            // Safety: classes in a `ClassList` are validated, so they're
+            // always nonempty and never contain whitespace.
+            unsafe { yew_classes.unchecked_push(AttrValue::from(String::from(class))) }
+        });
+
+        yew_classes
+    }
+}
+
+impl IntoPropValue<::yew::html::Classes> for ClassList {
+    #[inline]
+    fn into_prop_value(self) -> ::yew::html::Classes {
+        self.into()
+    }
+}
+
+impl IntoPropValue<AttrValue> for ClassList {
+    #[inline]
+    fn into_prop_value(self) -> AttrValue {
+        AttrValue::Rc(Rc::from(self.to_string()))
+    }
+}
+
+impl IntoPropValue<Option<AttrValue>> for ClassList {
+    #[inline]
+    fn into_prop_value(self) -> Option<AttrValue> {
+        match self.is_empty() {
+            true => None,
+            false => Some(self.into_prop_value()),
+        }
+    }
+}
+
+impl ToHtml for ClassList {
+    #[inline]
+    fn to_html(&self) -> Html {
+        Html::VText(VText::new(self.to_string()))
+    }
+}
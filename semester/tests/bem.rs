@@ -0,0 +1,44 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use semester::{bem, static_bem, Classes as _, StaticClasses as _};
+
+#[test]
+fn test_bem() {
+    let header = |collapsed: bool, sticky: bool| bem!("card", "header"; "collapsed": collapsed, "sticky": sticky);
+
+    assert_eq!(header(false, false).render(), "card__header");
+    assert_eq!(
+        header(true, true).render(),
+        "card__header card__header--collapsed card__header--sticky"
+    );
+
+    assert_eq!(bem!("card").render(), "card");
+    assert_eq!(bem!("card", "body").try_as_str(), Some("card__body"));
+    assert_eq!(
+        bem!("card"; "wide", "flat": false).render(),
+        "card card--wide"
+    );
+}
+
+#[test]
+fn test_static_bem() {
+    let header = |collapsed: bool| static_bem!("card", "header"; "collapsed": collapsed);
+
+    assert_eq!(header(false).as_str(), "card__header");
+    assert_eq!(
+        header(true).as_str(),
+        "card__header card__header--collapsed"
+    );
+}
+
+#[test]
+fn test_bem_prefix() {
+    let active = true;
+    let button = bem!(prefix = "acme-"; "button"; "active": active);
+
+    assert_eq!(button.render(), "acme-button acme-button--active");
+}
@@ -0,0 +1,50 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use semester::{classes, static_classes, Classes as _, StaticClasses as _};
+
+#[test]
+fn test_classes_module() {
+    let build = |active: bool, disabled: bool| {
+        classes!(
+            mod "tests/css/button.module.css";
+            "button",
+            "active": active,
+            "disabled": disabled,
+        )
+    };
+
+    assert_eq!(build(false, false).render(), "button_x7f2a");
+    assert_eq!(
+        build(true, true).render(),
+        "button_x7f2a active_b81c3 disabled_03d9e"
+    );
+}
+
+#[test]
+fn test_static_classes_module() {
+    let build = |active: bool| {
+        static_classes!(
+            mod "tests/css/button.module.css.json";
+            "button",
+            "active": active,
+        )
+    };
+
+    assert_eq!(build(false).as_str(), "button_x7f2a");
+    assert_eq!(build(true).as_str(), "button_x7f2a active_b81c3");
+}
+
+#[test]
+fn test_module_global() {
+    let classes = classes!(
+        mod "tests/css/button.module.css";
+        "button",
+        ::"clearfix",
+    );
+
+    assert_eq!(classes.render(), "button_x7f2a clearfix");
+}
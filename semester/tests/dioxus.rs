@@ -0,0 +1,62 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+#![cfg(feature = "dioxus")]
+
+use dioxus::{
+    dioxus_core::{AttributeValue, IntoAttributeValue},
+    prelude::*,
+};
+use semester::{classes, static_classes, ClassList};
+
+#[component]
+fn Button(active: bool) -> Element {
+    let disabled = false;
+
+    rsx! {
+        button {
+            class: classes!("button", "active": active, "disabled": disabled),
+            "data-state": static_classes!("state-active": active, "state-disabled": disabled),
+            "Click"
+        }
+    }
+}
+
+fn app() -> Element {
+    let list: ClassList = "extra1 extra2".parse().unwrap();
+
+    rsx! {
+        div { class: list,
+            Button { active: true }
+            Button { active: false }
+        }
+    }
+}
+
+#[test]
+fn test_dioxus_ssr() {
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        "<div class=\"extra1 extra2\">\
+         <button class=\"button active\" data-state=\"state-active\">Click</button>\
+         <button class=\"button\">Click</button>\
+         </div>"
+    );
+}
+
+#[test]
+fn test_dioxus_attribute_value() {
+    let (c1, c2) = (true, false);
+
+    let value = classes!("class1", "maybe1": c1, "maybe2": c2).into_value();
+    assert!(matches!(value, AttributeValue::Text(ref text) if text == "class1 maybe1"));
+
+    let value = static_classes!("maybe1": c2, "maybe2": c2).into_value();
+    assert!(matches!(value, AttributeValue::None));
+}
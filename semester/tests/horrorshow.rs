@@ -0,0 +1,46 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+#![cfg(feature = "horrorshow")]
+
+use horrorshow::{html, Template};
+use semester::{classes, static_classes, ClassList};
+
+#[test]
+fn test_horrorshow_attr() {
+    let (c1, c2) = (true, false);
+    let dynamic = classes!("class1", "maybe1": c1, "maybe2": c2);
+    let fixed = classes!("class2", "class3");
+
+    let rendered = html! {
+        div(class = &dynamic) {
+            p(class = fixed) : "text";
+            p(class = static_classes!("maybe1": c2, "maybe2": !c2));
+        }
+    }
+    .into_string()
+    .unwrap();
+
+    assert_eq!(
+        rendered,
+        r#"<div class="class1 maybe1"><p class="class2 class3">text</p><p class="maybe2"></p></div>"#
+    );
+}
+
+#[test]
+fn test_horrorshow_content() {
+    let c1 = true;
+    let list: ClassList = "class1 class2".parse().unwrap();
+
+    let rendered = html! {
+        p : classes!("class1", "maybe1": c1);
+        p : &list;
+    }
+    .into_string()
+    .unwrap();
+
+    assert_eq!(rendered, "<p>class1 maybe1</p><p>class1 class2</p>");
+}
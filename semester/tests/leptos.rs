@@ -0,0 +1,62 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+#![cfg(feature = "leptos")]
+
+use leptos::prelude::*;
+use semester::{classes, static_classes, ClassList};
+
+#[component]
+fn Button(active: bool, disabled: ReadSignal<bool>) -> impl IntoView {
+    view! {
+        <button
+            class=classes!("button", "active": active)
+            data-state=move || static_classes!("state-disabled": disabled.get(), "state-active": active)
+        >
+            "Click"
+        </button>
+    }
+}
+
+#[test]
+fn test_leptos_ssr() {
+    let owner = Owner::new();
+    owner.set();
+
+    let (disabled, _) = signal(true);
+    let list: ClassList = "extra1 extra2".parse().unwrap();
+
+    let rendered = view! {
+        <div class=list>
+            <Button active=true disabled=disabled />
+            <Button active=false disabled=disabled />
+        </div>
+    }
+    .to_html();
+
+    assert_eq!(
+        rendered,
+        "<div class=\"extra1 extra2\">\
+         <button data-state=\"state-disabled state-active\" class=\"button active\">Click</button>\
+         <button data-state=\"state-disabled\" class=\"button\">Click</button>\
+         </div>"
+    );
+}
+
+#[test]
+fn test_leptos_reactive() {
+    let owner = Owner::new();
+    owner.set();
+
+    let (active, set_active) = signal(false);
+    let button = move || {
+        view! { <button class=move || classes!("button", "active": active.get()) /> }.to_html()
+    };
+
+    assert_eq!(button(), r#"<button class="button"></button>"#);
+    set_active.set(true);
+    assert_eq!(button(), r#"<button class="button active"></button>"#);
+}
@@ -0,0 +1,54 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+#![cfg(feature = "maud")]
+
+use maud::html;
+use semester::{classes, maud::optional, static_classes, ClassList};
+
+#[test]
+fn test_maud_attr() {
+    let (c1, c2) = (true, false);
+    let dynamic = classes!("class1", "maybe1": c1, "maybe2": c2);
+
+    let markup = html! {
+        div class=(dynamic) {
+            p class=(classes!("class2", "class3")) { "text" }
+            p class=(static_classes!("maybe1": c2, "maybe2": !c2)) {}
+        }
+    };
+
+    assert_eq!(
+        markup.into_string(),
+        r#"<div class="class1 maybe1"><p class="class2 class3">text</p><p class="maybe2"></p></div>"#
+    );
+}
+
+#[test]
+fn test_maud_optional() {
+    let (c1, c2) = (true, false);
+
+    let markup = html! {
+        p class=[optional(classes!("maybe1": c1, "maybe2": c2))] {}
+        p class=[optional(static_classes!("maybe1": c2, "maybe2": c2))] {}
+        p class=(classes!("maybe1": c2)) {}
+    };
+
+    assert_eq!(
+        markup.into_string(),
+        r#"<p class="maybe1"></p><p></p><p class=""></p>"#
+    );
+}
+
+#[test]
+fn test_maud_class_list() {
+    let list: ClassList = "class1 class2".parse().unwrap();
+
+    assert_eq!(
+        html! { p class=(list) {} }.into_string(),
+        r#"<p class="class1 class2"></p>"#
+    );
+}
@@ -0,0 +1,135 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::borrow::Cow;
+
+use cool_asserts::assert_matches;
+use semester::{classes, static_classes, Classes as _, MergeGroup, MergeTable};
+
+#[test]
+fn test_merge_overrides() {
+    let build = |big: bool| classes!("card", "p-4", "text-sm", "text-lg": big, "rounded");
+
+    assert_eq!(
+        build(false).merge("p-8 text-xl").to_string(),
+        "card rounded p-8 text-xl"
+    );
+    assert_eq!(
+        build(true).merge("p-8 text-xl").to_string(),
+        "card rounded p-8 text-xl"
+    );
+
+    // Overrides can also be another set of classes
+    assert_eq!(
+        build(false)
+            .merge(static_classes!("rounded-none", "shadow"))
+            .to_string(),
+        "card p-4 text-sm rounded-none shadow"
+    );
+}
+
+#[test]
+fn test_merge_rules() {
+    let merge = |overrides: &str| classes!("px-2", "p-4").merge(overrides).to_string();
+
+    // `p-4` overrides `px-2`, but not the other way around
+    assert_eq!(classes!("px-2", "p-4").merge("").to_string(), "p-4");
+    assert_eq!(merge("px-3"), "p-4 px-3");
+    assert_eq!(merge("p-2"), "p-2");
+
+    // Variants and the `!` modifier must match
+    assert_eq!(merge("md:p-2"), "p-4 md:p-2");
+    assert_eq!(merge("!p-2"), "p-4 !p-2");
+    assert_eq!(
+        classes!("md:hover:p-2").merge("md:hover:p-4").to_string(),
+        "md:hover:p-4"
+    );
+
+    // Groups that depend on the value
+    assert_eq!(
+        classes!("text-lg", "text-red-500")
+            .merge("text-blue-500")
+            .to_string(),
+        "text-lg text-blue-500"
+    );
+    assert_eq!(
+        classes!("border-2", "border-red-500")
+            .merge("border-4")
+            .to_string(),
+        "border-red-500 border-4"
+    );
+
+    // Duplicates are kept in their last position, and invalid classes are
+    // dropped
+    assert_eq!(merge("flex <bad> p-4 flex"), "p-4 flex");
+
+    // Unrecognized classes never conflict
+    assert_eq!(
+        classes!("btn", "[mask-type:alpha]")
+            .merge("btn-primary [mask-type:luminance]")
+            .to_string(),
+        "btn [mask-type:alpha] btn-primary [mask-type:luminance]"
+    );
+}
+
+#[test]
+fn test_merge_custom_groups() {
+    const TABLE: MergeTable = MergeTable::TAILWIND
+        .with_groups(&[
+            MergeGroup::new("button-size").with_classes(&["btn-sm", "btn-lg"]),
+            MergeGroup::new("elevation").with_roots(&["elevation"]),
+            MergeGroup::new("elevation-color").with_roots(&["elevation-color"]),
+        ])
+        .with_includes(&[("elevation", &["elevation-color"])])
+        .with_prefix("tw-");
+
+    let base = classes!(
+        "btn",
+        "btn-sm",
+        "elevation-2",
+        "elevation-color-red",
+        "tw-p-4",
+        "p-4"
+    );
+
+    assert_eq!(
+        base.merge_with("btn-lg elevation-4 tw-p-8", &TABLE)
+            .to_string(),
+        "btn p-4 btn-lg elevation-4 tw-p-8"
+    );
+    assert_eq!(
+        base.merge_with("elevation-color-blue", &TABLE).to_string(),
+        "btn btn-sm elevation-2 tw-p-4 p-4 elevation-color-blue"
+    );
+
+    // Without the built-in groups, only the custom groups conflict
+    const CUSTOM: MergeTable =
+        MergeTable::new().with_groups(&[MergeGroup::new("button-size").with_roots(&["btn-size"])]);
+
+    assert_eq!(
+        classes!("btn-size-sm", "p-4")
+            .merge_with("btn-size-lg p-8", &CUSTOM)
+            .to_string(),
+        "p-4 btn-size-lg p-8"
+    );
+}
+
+#[test]
+fn test_merge_render() {
+    let active = false;
+    let base = classes!("btn", "p-4", "active": active);
+
+    // Nothing is allocated if the merged set is the same as the base set
+    assert_matches!(base.merge("").render(), Cow::Borrowed("btn p-4"));
+    assert_matches!(base.merge("  <bad> ").render(), Cow::Borrowed("btn p-4"));
+    assert_eq!(base.merge("").len(), 2);
+
+    let merged = base.merge("p-2 active");
+    assert_matches!(merged.render(), Cow::Owned(rendered) => assert_eq!(rendered, "btn p-2 active"));
+    assert_eq!(merged.len(), 3);
+    assert!(!merged.is_empty());
+    assert!(static_classes!().merge("").is_empty());
+}
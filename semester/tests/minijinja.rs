@@ -0,0 +1,68 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+#![cfg(feature = "minijinja")]
+
+use minijinja::{context, Environment, Value};
+use semester::{classes, static_classes, ClassList};
+
+fn env() -> Environment<'static> {
+    let mut env = Environment::new();
+    semester::minijinja::register(&mut env);
+    env
+}
+
+#[test]
+fn test_minijinja_value() {
+    let (c1, c2) = (true, false);
+    let dynamic: Value = classes!("class1", "maybe1": c1, "maybe2": c2).into();
+    let fixed: Value = static_classes!("class2", "class3").into();
+    let list = Value::from(ClassList::parse("class4").unwrap());
+    let ctx = context! { dynamic, fixed, list };
+
+    let template = "\
+        {{ dynamic }}|{{ dynamic | length }}|{{ 'maybe1' in dynamic }}|{{ 'maybe' in dynamic }}|\
+        {{ fixed | join(',') }}|{{ fixed[1] }}|{{ list }}";
+
+    assert_eq!(
+        env().render_str(template, ctx).unwrap(),
+        "class1 maybe1|2|True|False|class2,class3|class3|class4"
+    );
+}
+
+#[test]
+fn test_minijinja_empty() {
+    let c1 = false;
+    let classes: Value = classes!("maybe1": c1).into();
+    let ctx = context! { classes };
+
+    assert_eq!(
+        env()
+            .render_str("{% if classes %}yes{% else %}no{% endif %}", ctx)
+            .unwrap(),
+        "no"
+    );
+}
+
+#[test]
+fn test_minijinja_classes_function() {
+    let template = "\
+        {{ classes('class1 class2', {'maybe-1': flag, 'maybe-2': not flag}, class1=true, never=0) }}";
+
+    assert_eq!(
+        env()
+            .render_str(template, context! { flag => true })
+            .unwrap(),
+        "class1 class2 maybe-1"
+    );
+
+    let err = env()
+        .render_str("{{ classes({'a&b': true}) }}", context! {})
+        .unwrap_err();
+    assert!(err.to_string().contains("\"a&b\""));
+
+    assert!(env().render_str("{{ classes(1) }}", context! {}).is_err());
+}
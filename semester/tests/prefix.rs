@@ -0,0 +1,46 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use semester::{classes, static_classes, Classes as _, StaticClasses as _};
+
+#[test]
+fn test_classes_prefix() {
+    let build = |active: bool| {
+        classes!(
+            prefix = "acme-";
+            "btn",
+            "btn-primary",
+            ::"clearfix",
+            "active": active,
+        )
+    };
+
+    assert_eq!(build(false).render(), "acme-btn acme-btn-primary clearfix");
+    assert_eq!(
+        build(true).render(),
+        "acme-btn acme-btn-primary clearfix acme-active"
+    );
+}
+
+#[test]
+fn test_static_classes_prefix() {
+    let build = |active: bool| static_classes!(prefix = "acme-"; "btn", "active": active);
+
+    assert_eq!(build(false).as_str(), "acme-btn");
+    assert_eq!(build(true).as_str(), "acme-btn acme-active");
+}
+
+#[test]
+fn test_prefix_with_module() {
+    let classes = classes!(
+        prefix = "acme-";
+        mod "tests/css/button.module.css";
+        "button",
+        ::"active",
+    );
+
+    assert_eq!(classes.render(), "button_x7f2a active");
+}
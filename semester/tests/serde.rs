@@ -0,0 +1,145 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+#![cfg(feature = "serde")]
+
+use semester::{classes, static_classes, ClassList, Classes, StaticClasses};
+use serde::Serialize;
+
+#[test]
+fn test_serialize_dynamic() {
+    let classes = classes!("class1", "maybe1": true, "maybe2": false, "class2");
+
+    assert_eq!(
+        serde_json::to_string(&classes).unwrap(),
+        r#""class1 maybe1 class2""#
+    );
+}
+
+#[test]
+fn test_serialize_seq() {
+    #[derive(Serialize)]
+    struct Fragment<C: Classes> {
+        #[serde(with = "semester::serde::seq")]
+        classes: C,
+    }
+
+    let fragment = Fragment {
+        classes: static_classes!("class1", "maybe1": false, "maybe2": true),
+    };
+
+    assert_eq!(
+        serde_json::to_string(&fragment).unwrap(),
+        r#"{"classes":["class1","maybe2"]}"#
+    );
+}
+
+#[test]
+fn test_deserialize_dynamic() {
+    fn build(c1: bool, c2: bool) -> impl Classes + serde::de::DeserializeOwned {
+        classes!("class1", "class2", "maybe1": c1, "class3", "maybe2": c2)
+    }
+
+    let mut classes = build(false, false);
+    assert_eq!(classes.render(), "class1 class2 class3");
+
+    classes = serde_json::from_str(r#""class1 class2 class3 maybe2""#).unwrap();
+    assert_eq!(classes.render(), "class1 class2 class3 maybe2");
+    assert_eq!(classes.len(), 4);
+
+    classes = serde_json::from_str(r#"["maybe1", "class1", "class2", "class3"]"#).unwrap();
+    assert_eq!(classes.render(), "class1 class2 maybe1 class3");
+
+    let unknown = serde_json::from_str(r#""class1 class2 class3 other""#).map(|c| classes = c);
+    assert!(unknown
+        .unwrap_err()
+        .to_string()
+        .contains("unknown class `other`"));
+
+    let missing = serde_json::from_str(r#""class1 class3 maybe1""#).map(|c| classes = c);
+    assert!(missing
+        .unwrap_err()
+        .to_string()
+        .contains("missing required class `class2`"));
+
+    let duplicate =
+        serde_json::from_str(r#"["class1", "class2", "class3", "class1"]"#).map(|c| classes = c);
+    assert!(duplicate
+        .unwrap_err()
+        .to_string()
+        .contains("duplicate class `class1`"));
+}
+
+#[test]
+fn test_deserialize_static() {
+    let (c1, c2) = (true, false);
+    let mut classes = static_classes!("class1", "maybe1": c1, "maybe2": c2);
+    assert_eq!(classes.as_str(), "class1 maybe1");
+
+    classes = serde_json::from_str(r#""class1 maybe2""#).unwrap();
+    assert_eq!(classes.as_str(), "class1 maybe2");
+    assert_eq!(classes.class_set(), ["class1", "maybe2"]);
+
+    assert!(serde_json::from_str(r#""maybe2""#)
+        .map(|c| classes = c)
+        .is_err());
+}
+
+#[test]
+fn test_deserialize_fixed() {
+    let mut classes = classes!("class1", "class2");
+    assert_eq!(classes.len(), 2);
+
+    classes = serde_json::from_str(r#"["class2", "class1"]"#).unwrap();
+    assert_eq!(classes.as_str(), "class1 class2");
+
+    assert!(serde_json::from_str(r#""class1""#)
+        .map(|c| classes = c)
+        .is_err());
+}
+
+#[test]
+fn test_round_trip_class_list() {
+    let list: ClassList = serde_json::from_str(r#"["card", "active", "card"]"#).unwrap();
+    assert_eq!(list.len(), 2);
+    assert_eq!(serde_json::to_string(&list).unwrap(), r#""card active""#);
+
+    let list: ClassList = serde_json::from_str(r#""card  active""#).unwrap();
+    assert_eq!(list.to_string(), "card active");
+
+    assert!(serde_json::from_str::<ClassList>(r#"["<script>"]"#).is_err());
+}
+
+#[test]
+fn test_round_trip_bincode() {
+    #[derive(Serialize, serde::Deserialize)]
+    struct Fragment<C: Classes> {
+        classes: C,
+        #[serde(with = "semester::serde::seq")]
+        seq: C,
+    }
+
+    fn build(active: bool) -> Fragment<impl Classes + Serialize + serde::de::DeserializeOwned> {
+        let classes = || classes!("card", "active": active, "wide": !active);
+
+        Fragment {
+            classes: classes(),
+            seq: classes(),
+        }
+    }
+
+    let mut fragment = build(true);
+    assert_eq!(fragment.classes.render(), "card active");
+
+    let encoded = bincode::serialize(&build(false)).unwrap();
+    fragment = bincode::deserialize(&encoded).unwrap();
+    assert_eq!(fragment.classes.render(), "card wide");
+    assert_eq!(fragment.seq.render(), "card wide");
+
+    let list = ClassList::parse("card active").unwrap();
+    let encoded = bincode::serialize(&list).unwrap();
+    assert_eq!(bincode::deserialize::<ClassList>(&encoded).unwrap(), list);
+}
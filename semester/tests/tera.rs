@@ -0,0 +1,87 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+#![cfg(feature = "tera")]
+
+use semester::{classes, static_classes, ClassList};
+use tera::{Context, Tera, Value};
+
+fn render(template: &str, context: &Context) -> tera::Result<String> {
+    let mut tera = Tera::default();
+    semester::tera::register(&mut tera);
+    tera.render_str(template, context)
+}
+
+#[test]
+fn test_tera_value() {
+    let (c1, c2) = (true, false);
+
+    let mut context = Context::new();
+    let dynamic: Value = classes!("class1", "maybe1": c1, "maybe2": c2).into();
+    let fixed: Value = static_classes!("class2", "class3").into();
+    context.insert("dynamic", &dynamic);
+    context.insert("fixed", &fixed);
+    context.insert("list", &Value::from(ClassList::parse("class4").unwrap()));
+
+    let template = "\
+        {{ dynamic }}|{{ dynamic | class_count }}|\
+        {{ dynamic is has_class('maybe1') }}|{{ dynamic is has_class('maybe') }}|\
+        {{ fixed }}|{{ list }}";
+
+    assert_eq!(
+        render(template, &context).unwrap(),
+        "class1 maybe1|2|true|false|class2 class3|class4"
+    );
+}
+
+#[test]
+fn test_tera_classes_function() {
+    let mut context = Context::new();
+    context.insert("flag", &true);
+
+    assert_eq!(
+        render(
+            "{{ classes(zeta=true, alpha=flag, beta=0, gamma='') }}",
+            &context
+        )
+        .unwrap(),
+        "alpha zeta"
+    );
+
+    assert_eq!(
+        render("{{ classes(a=1) | class_count }}", &context).unwrap(),
+        "1"
+    );
+    assert!(render("{{ 1 | class_count }}", &context).is_err());
+}
+
+#[test]
+fn test_tera_classes_map() {
+    let mut context = Context::new();
+    context.insert(
+        "states",
+        &serde_json::json!({ "card-wide": true, "card-dark": false, "is-active": 1 }),
+    );
+    context.insert("invalid", &serde_json::json!({ "a<b": true }));
+
+    assert_eq!(
+        render("{{ classes(map=states) }}", &context).unwrap(),
+        "card-wide is-active"
+    );
+
+    // Keyword arguments override the map
+    assert_eq!(
+        render("{{ classes(map=states, card=true, wide=false) }}", &context).unwrap(),
+        "card card-wide is-active"
+    );
+
+    assert!(render("{{ classes(map=invalid) }}", &context).is_err());
+
+    // `map` must be an object
+    let err = render("{{ classes(map='x') }}", &context).unwrap_err();
+    assert!(format!("{err:?}").contains("expects `map` to be an object"));
+    assert!(render("{{ classes(map=false) }}", &context).is_err());
+}
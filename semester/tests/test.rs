@@ -7,7 +7,10 @@
 use std::borrow::Cow;
 
 use cool_asserts::assert_matches;
-use semester::{classes, static_classes, Classes, StaticClasses};
+use semester::{
+    classes, static_classes, ClassFilter, ClassList, ClassNameError, Classes, Rejected, Rejection,
+    StaticClasses,
+};
 
 #[test]
 fn test_static_classes() {
@@ -149,3 +152,141 @@ fn test_fixed_dynamic() {
     assert_eq!(classes.class_set(), &["class1", "class2", "class3"]);
     assert_eq!(classes.to_string(), "class1 class2 class3")
 }
+
+#[test]
+fn test_attr() {
+    let (c1, c2) = (true, false);
+
+    let classes = classes!("class1": c1, "class2": c2);
+    assert_eq!(format!("<p{}>", classes.attr()), r#"<p class="class1">"#);
+    assert_eq!(
+        format!("<p{}>", classes.attr_named("data-classes")),
+        r#"<p data-classes="class1">"#
+    );
+
+    let classes = static_classes!("class1": c2, "class2": c2);
+    assert_eq!(format!("<p{}>", classes.attr()), "<p>");
+    assert_eq!(format!("<p{}>", classes!().attr()), "<p>");
+}
+
+#[test]
+fn test_class_list_attr() {
+    let list: ClassList = "class1 class2".parse().unwrap();
+    assert_eq!(
+        format!("<p{}>", list.attr()),
+        r#"<p class="class1 class2">"#
+    );
+    assert_eq!(format!("<p{}>", ClassList::new().attr()), "<p>");
+}
+
+#[test]
+fn test_with_extra() {
+    let (c1, c2) = (true, false);
+
+    let classes = classes!("class1", "class2", "maybe1": c2);
+    assert_matches!(
+        classes.with_extra("").render(),
+        Cow::Borrowed("class1 class2")
+    );
+    assert_matches!(
+        classes.with_extra(" class2 ").render(),
+        Cow::Borrowed("class1 class2")
+    );
+
+    let classes = classes!("class1", "maybe1": c1, "maybe2": c2);
+    let extra = classes.with_extra("maybe2 class1 extra\t<bad> extra");
+    assert_eq!(extra.to_string(), "class1 maybe1 maybe2 extra");
+    assert_eq!(extra.render(), "class1 maybe1 maybe2 extra");
+    assert_eq!(extra.len(), 4);
+
+    let extra = classes!().with_extra_owned(String::from("extra"));
+    assert_eq!(extra.to_string(), "extra");
+    assert_eq!(extra.render(), "extra");
+}
+
+#[test]
+fn test_universe() {
+    let (c1, c2) = (true, false);
+
+    let classes = classes!("class1", "maybe1": c1, "never": false, "maybe2": c2);
+    assert_eq!(classes.universe(), ["class1", "maybe1", "maybe2"]);
+
+    let classes = static_classes!("class1", "maybe1": c1, "maybe2": c2);
+    assert_eq!(classes.universe(), ["class1", "maybe1", "maybe2"]);
+
+    assert_eq!(
+        classes!("class1", "class2").universe(),
+        ["class1", "class2"]
+    );
+}
+
+#[test]
+fn test_universe_default() {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    struct Card;
+
+    impl std::fmt::Display for Card {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("card")
+        }
+    }
+
+    impl Classes for Card {
+        type Iter = std::iter::Once<&'static str>;
+
+        fn render(&self) -> Cow<'static, str> {
+            Cow::Borrowed("card")
+        }
+
+        fn try_as_str(&self) -> Option<&'static str> {
+            Some("card")
+        }
+
+        fn iter(&self) -> Self::Iter {
+            std::iter::once("card")
+        }
+
+        fn len(&self) -> usize {
+            1
+        }
+    }
+
+    assert!(Card.universe().is_empty());
+    assert!(ClassFilter::from_classes(&Card).check("card").is_err());
+}
+
+#[test]
+fn test_class_filter() {
+    // `maybe1` can never be enabled, so it isn't allowed
+    let filter = ClassFilter::from_classes(&classes!("class1", "maybe1": false))
+        .with_slice(&["theme-light", "theme-dark"]);
+
+    let filtered = filter.filter("theme-dark maybe1 class1 a&b theme-dark");
+    assert_eq!(filtered.classes.to_string(), "theme-dark class1");
+    assert_matches!(
+        filtered.rejected,
+        [
+            Rejected {
+                class: "maybe1",
+                reason: Rejection::NotAllowed
+            },
+            Rejected {
+                class: "a&b",
+                reason: Rejection::Invalid(ClassNameError::HtmlUnsafe)
+            },
+        ]
+    );
+
+    assert_eq!(filter.check("class1"), Ok("class1"));
+    assert_eq!(
+        filter.check(""),
+        Err(Rejection::Invalid(ClassNameError::Empty))
+    );
+
+    let err = filter.try_filter("class1 bogus").unwrap_err();
+    assert_eq!(err.rejected().len(), 1);
+    assert_eq!(
+        err.to_string(),
+        r#"rejected classes: "bogus": class is not in the allowlist"#
+    );
+}
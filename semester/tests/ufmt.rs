@@ -0,0 +1,40 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+#![cfg(feature = "ufmt")]
+
+use semester::{classes, static_classes};
+
+fn render(classes: &impl ufmt::uDisplay) -> String {
+    let mut buffer = String::new();
+    ufmt::uwrite!(buffer, "<div class=\"{}\">", classes).unwrap();
+    buffer
+}
+
+#[test]
+fn test_ufmt_dynamic() {
+    let (c1, c2) = (true, false);
+    let classes = classes!("class1", "maybe1": c1, "maybe2": c2, "class2");
+
+    assert_eq!(render(&classes), r#"<div class="class1 maybe1 class2">"#);
+}
+
+#[test]
+fn test_ufmt_static() {
+    let (c1, c2) = (false, true);
+    let classes = static_classes!("class1", "maybe1": c1, "maybe2": c2);
+
+    assert_eq!(render(&classes), r#"<div class="class1 maybe2">"#);
+}
+
+#[test]
+fn test_ufmt_fixed() {
+    assert_eq!(
+        render(&classes!("class1", "class2")),
+        r#"<div class="class1 class2">"#
+    );
+    assert_eq!(render(&classes!()), r#"<div class="">"#);
+}
@@ -0,0 +1,67 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+#![cfg(feature = "unicode")]
+
+use semester::{
+    classes, static_classes, validate_class_name, ClassList, ClassNameError, Classes as _,
+    StaticClasses as _,
+};
+
+#[test]
+fn test_unicode_macros() {
+    let build = |active: bool| classes!("tarjeta", "botón", "アクティブ": active);
+
+    assert_eq!(build(false).render(), "tarjeta botón");
+    assert_eq!(build(true).render(), "tarjeta botón アクティブ");
+    assert_eq!(
+        build(true).attr().to_string(),
+        " class=\"tarjeta botón アクティブ\""
+    );
+
+    let button = static_classes!(prefix = "ü-"; "knopf", "größe": true);
+    assert_eq!(button.as_str(), "ü-knopf ü-größe");
+}
+
+#[test]
+fn test_unicode_validation() {
+    assert_eq!(validate_class_name("botón"), Ok(()));
+    assert_eq!(validate_class_name("日本語"), Ok(()));
+    assert_eq!(
+        validate_class_name("a\u{3000}b"),
+        Err(ClassNameError::Whitespace)
+    );
+    assert_eq!(
+        validate_class_name("a\u{a0}b"),
+        Err(ClassNameError::Whitespace)
+    );
+    assert_eq!(
+        validate_class_name("a\u{85}b"),
+        Err(ClassNameError::Whitespace)
+    );
+    assert_eq!(
+        validate_class_name("a\u{7f}b"),
+        Err(ClassNameError::Control)
+    );
+    assert_eq!(
+        validate_class_name("a\u{9b}b"),
+        Err(ClassNameError::Control)
+    );
+    assert_eq!(
+        validate_class_name("«a&b»"),
+        Err(ClassNameError::HtmlUnsafe)
+    );
+
+    let list = ClassList::parse("größe\u{2003}botón").unwrap();
+    assert_eq!(list.to_string(), "größe botón");
+    assert!(list.contains("botón"));
+    assert_eq!(
+        classes!("tarjeta")
+            .with_extra("größe\u{2003}botón")
+            .to_string(),
+        "tarjeta größe botón"
+    );
+}
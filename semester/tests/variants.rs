@@ -0,0 +1,54 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use semester::{classes, static_classes, Classes as _, StaticClasses as _};
+
+#[test]
+fn test_variant_group() {
+    let classes = classes!("flex-col", "md:" { "flex-row", "gap-4", "px-6" }, "p-2");
+
+    assert_eq!(
+        classes.try_as_str(),
+        Some("flex-col md:flex-row md:gap-4 md:px-6 p-2")
+    );
+}
+
+#[test]
+fn test_nested_variant_groups() {
+    let build = |wide: bool| {
+        classes!(
+            "dark:" {
+                "bg-black",
+                "hover:" { "bg-gray-800", "underline": wide },
+            },
+            "md:" { "w-full": wide },
+        )
+    };
+
+    assert_eq!(
+        build(false).render(),
+        "dark:bg-black dark:hover:bg-gray-800"
+    );
+    assert_eq!(
+        build(true).render(),
+        "dark:bg-black dark:hover:bg-gray-800 dark:hover:underline md:w-full"
+    );
+}
+
+#[test]
+fn test_static_variant_groups() {
+    let build = |active: bool| static_classes!("btn", "hover:" { "shadow", "ring": active });
+
+    assert_eq!(build(false).as_str(), "btn hover:shadow");
+    assert_eq!(build(true).as_str(), "btn hover:shadow hover:ring");
+}
+
+#[test]
+fn test_variant_groups_with_prefix() {
+    let classes = classes!(prefix = "tw-"; "flex", "md:" { "grid", ::"clearfix" });
+
+    assert_eq!(classes.render(), "tw-flex md:tw-grid md:clearfix");
+}
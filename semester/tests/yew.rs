@@ -0,0 +1,132 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+#![cfg(feature = "yew")]
+
+use semester::{classes, static_classes, ClassList};
+use yew::{html, html::IntoPropValue, AttrValue, Component, Context, Html, LocalServerRenderer};
+
+#[derive(Default, PartialEq, yew::Properties)]
+struct Props {
+    classes: yew::Classes,
+    title: Option<AttrValue>,
+}
+
+struct Button;
+
+impl Component for Button {
+    type Message = ();
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        html! { <button class={props.classes.clone()} title={props.title.clone()} /> }
+    }
+}
+
+struct App;
+
+impl Component for App {
+    type Message = ();
+    type Properties = ();
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self
+    }
+
+    fn view(&self, _ctx: &Context<Self>) -> Html {
+        let (c1, c2) = (true, false);
+
+        html! {
+            <div class={classes!("class1", "maybe1": c1, "maybe2": c2)}>
+                <Button
+                    classes={static_classes!("class2", "maybe1": c2)}
+                    title={classes!("maybe1": c2)}
+                />
+                <p>{classes!("class3", "maybe2": !c2)}</p>
+            </div>
+        }
+    }
+}
+
+#[test]
+fn test_yew_ssr() {
+    let rendered =
+        futures::executor::block_on(LocalServerRenderer::<App>::new().hydratable(false).render());
+
+    assert_eq!(
+        rendered,
+        r#"<div class="class1 maybe1"><button class="class2"></button><p>class3 maybe2</p></div>"#
+    );
+}
+
+#[test]
+fn test_yew_attr_value() {
+    let (c1, c2) = (true, false);
+
+    let value: AttrValue = classes!("class1", "maybe1": c2).into_prop_value();
+    assert!(matches!(value, AttrValue::Static("class1")));
+
+    let value: AttrValue = classes!("class1", "maybe1": c1).into_prop_value();
+    assert!(matches!(value, AttrValue::Rc(ref s) if &**s == "class1 maybe1"));
+
+    let value: Option<AttrValue> = classes!("maybe1": c2).into_prop_value();
+    assert_eq!(value, None);
+}
+
+#[test]
+fn test_yew_classes() {
+    let c1 = true;
+
+    let classes: yew::Classes = classes!("class1", "maybe1": c1).into();
+    assert_eq!(classes, yew::classes!("class1", "maybe1"));
+
+    let list: ClassList = "class1 class2".parse().unwrap();
+    let classes: yew::Classes = list.into();
+    assert_eq!(classes, yew::classes!("class1", "class2"));
+}
+
+#[test]
+fn test_yew_to_classes_untrusted() {
+    // A `Classes` implementation outside of semester isn't validated, so its
+    // classes have to go through Yew's own checks.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    struct Untrusted;
+
+    impl std::fmt::Display for Untrusted {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("a b  c")
+        }
+    }
+
+    impl semester::Classes for Untrusted {
+        type Iter = std::array::IntoIter<&'static str, 3>;
+
+        fn render(&self) -> std::borrow::Cow<'static, str> {
+            "a b  c".into()
+        }
+
+        fn try_as_str(&self) -> Option<&'static str> {
+            Some("a b  c")
+        }
+
+        fn iter(&self) -> Self::Iter {
+            ["a b", "", "c"].into_iter()
+        }
+
+        fn len(&self) -> usize {
+            3
+        }
+    }
+
+    let classes = semester::yew::to_classes(&Untrusted);
+    assert_eq!(classes, yew::classes!("a", "b", "c"));
+    assert_eq!(classes.into_iter().count(), 3);
+}